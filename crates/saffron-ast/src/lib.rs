@@ -55,8 +55,9 @@ pub enum Unit {
     Percent,
 }
 
-/// Ingredient category enum (closed set)
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// Ingredient category enum (closed set), spelled in lowercase in the SID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum IngredientCategory {
     Protein,
     Fat,
@@ -67,6 +68,95 @@ pub enum IngredientCategory {
     Dairy,
 }
 
+impl IngredientCategory {
+    pub const ALL: [IngredientCategory; 7] = [
+        IngredientCategory::Protein,
+        IngredientCategory::Fat,
+        IngredientCategory::Carbohydrate,
+        IngredientCategory::Liquid,
+        IngredientCategory::Seasoning,
+        IngredientCategory::Produce,
+        IngredientCategory::Dairy,
+    ];
+
+    /// SID spelling, also used as the data directory name
+    pub fn as_str(self) -> &'static str {
+        match self {
+            IngredientCategory::Protein => "protein",
+            IngredientCategory::Fat => "fat",
+            IngredientCategory::Carbohydrate => "carbohydrate",
+            IngredientCategory::Liquid => "liquid",
+            IngredientCategory::Seasoning => "seasoning",
+            IngredientCategory::Produce => "produce",
+            IngredientCategory::Dairy => "dairy",
+        }
+    }
+}
+
+/// Declarable allergens: the union of the EU 14 (Regulation 1169/2011) and the US big 9
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Allergen {
+    /// Cereals containing gluten (EU)
+    Gluten,
+    /// Wheat (US; covered by `Gluten` in the EU)
+    Wheat,
+    Crustaceans,
+    Molluscs,
+    Eggs,
+    Fish,
+    Peanuts,
+    TreeNuts,
+    Soy,
+    Milk,
+    Sesame,
+    Celery,
+    Mustard,
+    Lupin,
+    /// Sulphur dioxide and sulphites above 10 mg/kg
+    Sulphites,
+}
+
+impl Allergen {
+    /// Listed under EU Regulation 1169/2011, Annex II
+    pub fn is_eu14(self) -> bool {
+        !matches!(self, Allergen::Wheat)
+    }
+
+    /// Listed under the US FALCPA / FASTER Act
+    pub fn is_us9(self) -> bool {
+        matches!(
+            self,
+            Allergen::Wheat
+                | Allergen::Crustaceans
+                | Allergen::Eggs
+                | Allergen::Fish
+                | Allergen::Peanuts
+                | Allergen::TreeNuts
+                | Allergen::Soy
+                | Allergen::Milk
+                | Allergen::Sesame
+        )
+    }
+}
+
+/// Calendar month, for seasonal availability
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Month {
+    Jan,
+    Feb,
+    Mar,
+    Apr,
+    May,
+    Jun,
+    Jul,
+    Aug,
+    Sep,
+    Oct,
+    Nov,
+    Dec,
+}
+
 /// Process type enum — the closed set of all culinary transformations
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ProcessType {
@@ -158,6 +248,43 @@ pub enum Doneness {
     Charred,
 }
 
+/// Texture states for expected results (e.g. `TextureState.Runny`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TextureState {
+    // Proteins
+    Raw,
+    Runny,
+    Jammy,
+    Set,
+    Firm,
+    Rubbery,
+    // Starches
+    Undercooked,
+    AlDente,
+    Tender,
+    Mushy,
+}
+
+/// Surface browning levels (e.g. `BrowningLevel.Light`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BrowningLevel {
+    None,
+    Light,
+    Medium,
+    Dark,
+    Burnt,
+}
+
+/// Perceived saltiness (e.g. `SeasoningLevel.WellSeasoned`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SeasoningLevel {
+    Unseasoned,
+    LightlySalted,
+    Salted,
+    WellSeasoned,
+    Overseasoned,
+}
+
 /// Phase of matter
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Phase {
@@ -206,41 +333,19 @@ pub struct TypeRef {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Expr {
     /// Numeric literal with unit: 180.celsius, 50.ml
-    UnitLiteral {
-        value: f64,
-        unit: Unit,
-        span: Span,
-    },
+    UnitLiteral { value: f64, unit: Unit, span: Span },
     /// Plain numeric literal: 42, 3.14
-    NumericLiteral {
-        value: f64,
-        span: Span,
-    },
+    NumericLiteral { value: f64, span: Span },
     /// Percentage literal: 76%
-    PercentLiteral {
-        value: f64,
-        span: Span,
-    },
+    PercentLiteral { value: f64, span: Span },
     /// String literal: "hello"
-    StringLiteral {
-        value: String,
-        span: Span,
-    },
+    StringLiteral { value: String, span: Span },
     /// Boolean literal: true, false
-    BoolLiteral {
-        value: bool,
-        span: Span,
-    },
+    BoolLiteral { value: bool, span: Span },
     /// Identifier reference: egg, pan, oil
-    Identifier {
-        name: String,
-        span: Span,
-    },
+    Identifier { name: String, span: Span },
     /// Enum variant: .Chicken, .StainlessSteel
-    EnumVariant {
-        variant: String,
-        span: Span,
-    },
+    EnumVariant { variant: String, span: Span },
     /// Field access: oil.state.temperature
     FieldAccess {
         object: Box<Expr>,
@@ -267,15 +372,9 @@ pub enum Expr {
         span: Span,
     },
     /// Array literal: [yolk, white]
-    Array {
-        elements: Vec<Expr>,
-        span: Span,
-    },
+    Array { elements: Vec<Expr>, span: Span },
     /// Lambda / condition: () => oil.temp >= 180.celsius
-    Lambda {
-        body: Box<Expr>,
-        span: Span,
-    },
+    Lambda { body: Box<Expr>, span: Span },
 }
 
 /// Destructuring pattern: -> [yolk, white]
@@ -380,15 +479,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sid_enum_spelling() {
+        let category = serde_json::to_string(&IngredientCategory::Protein).unwrap();
+        assert_eq!(category, "\"protein\"");
+        assert_eq!(IngredientCategory::Dairy.as_str(), "dairy");
+        let allergen: Allergen = serde_json::from_str("\"tree_nuts\"").unwrap();
+        assert_eq!(allergen, Allergen::TreeNuts);
+        let month: Month = serde_json::from_str("\"Sep\"").unwrap();
+        assert_eq!(month, Month::Sep);
+    }
+
+    #[test]
+    fn test_allergen_lists() {
+        let all = [
+            Allergen::Gluten,
+            Allergen::Wheat,
+            Allergen::Crustaceans,
+            Allergen::Molluscs,
+            Allergen::Eggs,
+            Allergen::Fish,
+            Allergen::Peanuts,
+            Allergen::TreeNuts,
+            Allergen::Soy,
+            Allergen::Milk,
+            Allergen::Sesame,
+            Allergen::Celery,
+            Allergen::Mustard,
+            Allergen::Lupin,
+            Allergen::Sulphites,
+        ];
+        assert_eq!(all.iter().filter(|a| a.is_eu14()).count(), 14);
+        assert_eq!(all.iter().filter(|a| a.is_us9()).count(), 9);
+    }
+
     #[test]
     fn test_recipe_roundtrip_json() {
         let span = Span {
             file: "test.saffron".into(),
-            start_line: 1, start_col: 1,
-            end_line: 1, end_col: 10,
-            byte_offset: 0, byte_length: 10,
+            start_line: 1,
+            start_col: 1,
+            end_line: 1,
+            end_col: 10,
+            byte_offset: 0,
+            byte_length: 10,
         };
-        
+
         let recipe = Recipe {
             name: "TestRecipe".into(),
             annotations: vec![],
@@ -0,0 +1,22 @@
+//! Packs `sid/data` into a binary bundle for the `embedded-sid` feature
+
+use std::path::{Path, PathBuf};
+
+fn main() {
+    if std::env::var_os("CARGO_FEATURE_EMBEDDED_SID").is_none() {
+        return;
+    }
+    let manifest = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let data = manifest.join("../../sid/data");
+    println!("cargo:rerun-if-changed={}", data.display());
+
+    let mut client = saffron_sid::SidClient::new();
+    if let Err(errors) = client.load_dir(&data) {
+        for err in &errors {
+            println!("cargo:warning=SID: {err}");
+        }
+        panic!("cannot embed {}: invalid SID data", data.display());
+    }
+    let out = Path::new(&std::env::var("OUT_DIR").unwrap()).join("sid.bundle");
+    std::fs::write(&out, client.to_bundle()).unwrap();
+}
@@ -10,10 +10,19 @@
 //!   saffron validate <file>    Validate against SLS
 //!   saffron fmt <file>         Format source code
 //!   saffron new <name>         Scaffold new recipe
-//!   saffron ingredient <name>  Query SID
+//!   saffron ingredient <name>  Query SID (or filter with --where "total_fat < 5")
 //!   saffron nutrition <file>   Compute nutrition facts
 
+use anyhow::{bail, Context};
 use clap::{Parser, Subcommand};
+use saffron_sid::bundle::Bundle;
+use saffron_sid::query::{NutrientQuery, Predicate, SortKey};
+use saffron_sid::{IngredientCategory, IngredientEntry, SidClient};
+use std::path::Path;
+
+/// `sid/data` packed by the build script
+#[cfg(feature = "embedded-sid")]
+static EMBEDDED_SID: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/sid.bundle"));
 
 #[derive(Parser)]
 #[command(name = "saffron")]
@@ -73,8 +82,26 @@ enum Commands {
     },
     /// Query the Saffron Ingredient Database
     Ingredient {
-        /// Ingredient name or ID to look up
-        name: String,
+        /// Ingredient name (any language) or ID to look up
+        name: Option<String>,
+        /// Nutrient filter per 100 g, e.g. "total_fat < 5" or "potassium in 300..600" (repeatable)
+        #[arg(long = "where", value_name = "PREDICATE")]
+        filters: Vec<String>,
+        /// Only this category, e.g. protein
+        #[arg(long)]
+        category: Option<String>,
+        /// Sort by a nutrient, e.g. potassium:desc
+        #[arg(long)]
+        sort: Option<String>,
+        /// Maximum number of results
+        #[arg(long)]
+        limit: Option<usize>,
+        /// SID data directory or bundle [default: the embedded bundle, else sid/data]
+        #[arg(long)]
+        data: Option<String>,
+        /// Directory of private or project ingredients layered over --data (repeatable, later wins)
+        #[arg(long = "overlay", value_name = "DIR")]
+        overlays: Vec<String>,
         /// Output as JSON
         #[arg(long)]
         json: bool,
@@ -84,6 +111,15 @@ enum Commands {
         /// Path to the .saffron source file
         file: String,
     },
+    /// Pack a SID data directory into a binary bundle
+    Bundle {
+        /// SID data directory
+        #[arg(long, default_value = "sid/data")]
+        data: String,
+        /// Bundle file to write
+        #[arg(short, long, default_value = "sid.bundle")]
+        output: String,
+    },
     /// Export recipe to another format
     Export {
         /// Path to the .saffron source file
@@ -123,17 +159,175 @@ fn main() {
             println!("Creating new recipe: {}", name);
             println!("TODO: Implement scaffolding (Phase 4)");
         }
-        Commands::Ingredient { name, json } => {
-            println!("Looking up ingredient: {}", name);
-            println!("TODO: Implement SID query (Phase 3)");
+        Commands::Ingredient {
+            name,
+            filters,
+            category,
+            sort,
+            limit,
+            data,
+            overlays,
+            json,
+        } => {
+            let query = IngredientQuery {
+                name,
+                filters,
+                category,
+                sort,
+                limit,
+            };
+            if let Err(err) = ingredient(data.as_deref(), &overlays, &query, json) {
+                eprintln!("error: {err:#}");
+                std::process::exit(1);
+            }
         }
         Commands::Nutrition { file } => {
             println!("Computing nutrition for {}...", file);
             println!("TODO: Implement nutrition calculator (Phase 3)");
         }
+        Commands::Bundle { data, output } => {
+            if let Err(err) = bundle(&data, &output) {
+                eprintln!("error: {err:#}");
+                std::process::exit(1);
+            }
+        }
         Commands::Export { file, format } => {
             println!("Exporting {} to {}...", file, format);
             println!("TODO: Implement export (Phase 4)");
         }
     }
 }
+
+/// Arguments of `saffron ingredient`
+struct IngredientQuery {
+    name: Option<String>,
+    filters: Vec<String>,
+    category: Option<String>,
+    sort: Option<String>,
+    limit: Option<usize>,
+}
+
+fn ingredient(
+    data: Option<&str>,
+    overlays: &[String],
+    args: &IngredientQuery,
+    json: bool,
+) -> anyhow::Result<()> {
+    let client = load_sid(data, overlays)?;
+
+    let mut query = NutrientQuery::new();
+    for filter in &args.filters {
+        query =
+            query.filter(Predicate::parse(filter).with_context(|| format!("--where {filter:?}"))?);
+    }
+    if let Some(category) = &args.category {
+        let Some(category) = IngredientCategory::ALL
+            .into_iter()
+            .find(|c| c.as_str() == category)
+        else {
+            bail!("unknown category `{category}`");
+        };
+        query = query.category(category);
+    }
+    if let Some(sort) = &args.sort {
+        query.sort = Some(SortKey::parse(sort).with_context(|| format!("--sort {sort:?}"))?);
+    }
+
+    let mut results: Vec<&IngredientEntry> = match &args.name {
+        Some(name) => match client.get(name) {
+            Some(entry) => vec![entry],
+            None => client.search(name),
+        },
+        None => client.query(&query),
+    };
+    if args.name.is_some() {
+        results.retain(|e| query.matches(e));
+        // Stable, so ties keep their search rank
+        if let Some(key) = &query.sort {
+            results.sort_by(|a, b| key.compare(a, b));
+        }
+    }
+    if let Some(limit) = args.limit {
+        results.truncate(limit);
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+        return Ok(());
+    }
+    if results.is_empty() {
+        println!("No matching ingredients");
+        return Ok(());
+    }
+    for entry in results {
+        let c = &entry.composition;
+        print!(
+            "{:<20} {:<24} {:<10} protein {:>5.1} g  fat {:>5.1} g  carbs {:>5.1} g",
+            entry.id,
+            entry.name.en,
+            entry.category.as_str(),
+            c.protein,
+            c.total_fat,
+            c.carbohydrates
+        );
+        if let Some(key) = &query.sort {
+            match key.field.value(c) {
+                Some(value) => print!("  {} {value}", key.field),
+                None => print!("  {} -", key.field),
+            }
+        }
+        println!();
+    }
+    Ok(())
+}
+
+/// The SID from `--data`, the embedded bundle or `sid/data`, with overlays on top
+fn load_sid(data: Option<&str>, overlays: &[String]) -> anyhow::Result<SidClient> {
+    let mut layers: Vec<&str> = overlays.iter().map(String::as_str).collect();
+    let mut client = match data {
+        Some(path) if Path::new(path).is_file() => {
+            SidClient::from_bundle(Bundle::open(path).with_context(|| format!("--data {path}"))?)
+        }
+        Some(dir) => {
+            layers.insert(0, dir);
+            SidClient::new()
+        }
+        #[cfg(feature = "embedded-sid")]
+        None => SidClient::from_bundle(Bundle::from_static(EMBEDDED_SID)?),
+        #[cfg(not(feature = "embedded-sid"))]
+        None => {
+            layers.insert(0, "sid/data");
+            SidClient::new()
+        }
+    };
+    for dir in layers {
+        if let Err(errors) = client.add_layer(dir, dir) {
+            for err in &errors {
+                eprintln!("warning: {err}");
+            }
+        }
+    }
+    if client.count() == 0 {
+        bail!("no ingredients loaded");
+    }
+    Ok(client)
+}
+
+fn bundle(data: &str, output: &str) -> anyhow::Result<()> {
+    let mut client = SidClient::new();
+    if let Err(errors) = client.load_dir(data) {
+        for err in &errors {
+            eprintln!("error: {err}");
+        }
+        bail!("{data} has {} invalid file(s)", errors.len());
+    }
+    let bytes = client.to_bundle();
+    std::fs::write(output, &bytes).with_context(|| format!("cannot write {output}"))?;
+    println!(
+        "Packed {} ingredients ({} bytes, SID {}) into {output}",
+        client.count(),
+        bytes.len(),
+        client.version()
+    );
+    Ok(())
+}
@@ -6,6 +6,9 @@
 //! - Human readable (.recipe.md) for recipe cards
 //! - Nutrition report (.nutrition.json)
 
+use saffron_ast::Unit;
+use saffron_physics::units::convert;
+
 pub struct CodeGenerator {
     // TODO: Phase 2 implementation
 }
@@ -14,4 +17,87 @@ impl CodeGenerator {
     pub fn new() -> Self {
         Self {}
     }
+
+    /// Format a quantity for a recipe card, with the other measurement system
+    /// in parentheses: `180 °C (356 °F)`, `1 cup (237 ml)`
+    pub fn format_quantity(&self, value: f64, unit: &Unit) -> String {
+        let alternate = match unit {
+            Unit::Celsius | Unit::Kelvin => Some(Unit::Fahrenheit),
+            Unit::Fahrenheit => Some(Unit::Celsius),
+            Unit::Grams | Unit::Milligrams => Some(Unit::Ounces),
+            Unit::Kilograms => Some(Unit::Pounds),
+            Unit::Ounces | Unit::Pounds => Some(Unit::Grams),
+            Unit::Milliliters | Unit::Liters => Some(Unit::Cups),
+            Unit::Cups | Unit::FluidOunces | Unit::Tablespoons | Unit::Teaspoons => {
+                Some(Unit::Milliliters)
+            }
+            Unit::Centimeters | Unit::Millimeters => Some(Unit::Inches),
+            Unit::Inches => Some(Unit::Centimeters),
+            _ => None,
+        };
+        let primary = format!("{} {}", round(value), symbol(unit));
+        match alternate.and_then(|alt| convert(value, unit, &alt).ok().map(|v| (v, alt))) {
+            Some((converted, alt)) => format!("{primary} ({} {})", round(converted), symbol(&alt)),
+            None => primary,
+        }
+    }
+}
+
+impl Default for CodeGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Round for display: whole numbers above 10, otherwise up to two decimals
+fn round(value: f64) -> String {
+    if value.abs() >= 10.0 {
+        format!("{value:.0}")
+    } else {
+        let s = format!("{value:.2}");
+        s.trim_end_matches('0').trim_end_matches('.').to_string()
+    }
+}
+
+fn symbol(unit: &Unit) -> &'static str {
+    match unit {
+        Unit::Celsius => "°C",
+        Unit::Fahrenheit => "°F",
+        Unit::Kelvin => "K",
+        Unit::Grams => "g",
+        Unit::Kilograms => "kg",
+        Unit::Ounces => "oz",
+        Unit::Pounds => "lb",
+        Unit::Milligrams => "mg",
+        Unit::Milliliters => "ml",
+        Unit::Liters => "l",
+        Unit::Cups => "cup",
+        Unit::Tablespoons => "tbsp",
+        Unit::Teaspoons => "tsp",
+        Unit::FluidOunces => "fl oz",
+        Unit::Seconds => "s",
+        Unit::Minutes => "min",
+        Unit::Hours => "h",
+        Unit::Centimeters => "cm",
+        Unit::Millimeters => "mm",
+        Unit::Inches => "in",
+        Unit::Joules => "J",
+        Unit::Calories => "cal",
+        Unit::Kilocalories => "kcal",
+        Unit::Watts => "W",
+        Unit::Percent => "%",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_quantity() {
+        let cg = CodeGenerator::new();
+        assert_eq!(cg.format_quantity(180.0, &Unit::Celsius), "180 °C (356 °F)");
+        assert_eq!(cg.format_quantity(1.0, &Unit::Cups), "1 cup (237 ml)");
+        assert_eq!(cg.format_quantity(3.0, &Unit::Minutes), "3 min");
+    }
 }
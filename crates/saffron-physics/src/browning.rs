@@ -0,0 +1,313 @@
+//! # Browning: Maillard Reaction and Caramelization
+//!
+//! Two independent kinetic models that accumulate a browning index over a
+//! surface temperature history:
+//!
+//! - **Maillard** — reducing sugars + amino acids. Arrhenius in temperature
+//!   (Ea ≈ 120 kJ/mol), faster at higher pH, and peaking at intermediate water
+//!   activity (a_w ≈ 0.7); a wet surface barely browns.
+//! - **Caramelization** — thermal decomposition of sugars alone, with separate
+//!   kinetics for sucrose, glucose and fructose (fructose starts ~50°C lower).
+//!
+//! Neither model has a hard temperature cutoff: rates simply become negligible
+//! at low temperature. The combined index maps onto `BrowningLevel`.
+
+use crate::denaturation::ArrheniusKinetics;
+use saffron_ast::BrowningLevel;
+use saffron_sid::Composition;
+use serde::{Deserialize, Serialize};
+
+/// Browning index thresholds for `Light`, `Medium`, `Dark` and `Burnt`
+const LEVEL_THRESHOLDS: [f64; 4] = [0.1, 0.3, 0.6, 1.5];
+
+/// Maillard kinetics for a fully reactive, optimally dry surface at pH 6
+const MAILLARD_KINETICS: ArrheniusKinetics = ArrheniusKinetics {
+    activation_energy: 120_000.0,
+    reference_temp_c: 150.0,
+    reference_rate: 0.01,
+};
+
+/// Map an accumulated browning index to a level
+pub fn browning_level(index: f64) -> BrowningLevel {
+    if index < LEVEL_THRESHOLDS[0] {
+        BrowningLevel::None
+    } else if index < LEVEL_THRESHOLDS[1] {
+        BrowningLevel::Light
+    } else if index < LEVEL_THRESHOLDS[2] {
+        BrowningLevel::Medium
+    } else if index < LEVEL_THRESHOLDS[3] {
+        BrowningLevel::Dark
+    } else {
+        BrowningLevel::Burnt
+    }
+}
+
+/// Approximate water activity of a surface from its moisture content (g water / 100 g).
+///
+/// A rough sorption-isotherm fit: most fresh foods sit near a_w ≈ 1, and a_w
+/// only falls appreciably once the surface has dried below ~20% moisture.
+pub fn surface_water_activity(moisture_pct: f64) -> f64 {
+    1.0 - (-moisture_pct.max(0.0) / 12.0).exp()
+}
+
+// ---------------------------------------------------------------------------
+// Maillard
+// ---------------------------------------------------------------------------
+
+/// Maillard reaction parameters for one food surface
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MaillardModel {
+    /// Substrate availability (0.0–1.0) from reducing sugars and amino acids
+    pub reactivity: f64,
+    pub ph: f64,
+}
+
+impl MaillardModel {
+    /// Build from reducing sugar and amino acid (protein) content, g/100g
+    pub fn new(reducing_sugars_pct: f64, amino_acids_pct: f64, ph: f64) -> Self {
+        // Even "sugar-free" meat carries ~0.5% glucose and ribose, which USDA
+        // sugar figures omit; both substrates saturate.
+        let sugars = reducing_sugars_pct.max(0.0) + 0.5;
+        let amino = amino_acids_pct.max(0.0);
+        Self {
+            reactivity: sugars / (sugars + 2.0) * amino / (amino + 5.0),
+            ph,
+        }
+    }
+
+    /// Build from a SID composition, assuming pH 6 when none is recorded
+    pub fn from_composition(composition: &Composition) -> Self {
+        Self::new(
+            composition.sugar,
+            composition.protein,
+            composition.ph.unwrap_or(6.0),
+        )
+    }
+
+    /// Relative rate multiplier from pH; alkaline conditions accelerate browning
+    fn ph_factor(&self) -> f64 {
+        10f64.powf((self.ph.clamp(3.0, 10.0) - 6.0) / 3.0)
+    }
+
+    /// Browning index accumulated per second
+    pub fn rate(&self, temperature_c: f64, water_activity: f64) -> f64 {
+        let aw = ((water_activity - 0.7) / 0.12).powi(2);
+        MAILLARD_KINETICS.rate(temperature_c) * self.reactivity * self.ph_factor() * (-aw).exp()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Caramelization
+// ---------------------------------------------------------------------------
+
+/// Sugars with distinct caramelization kinetics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Sugar {
+    /// Table sugar; caramelizes from ~160°C
+    Sucrose,
+    /// Caramelizes from ~160°C
+    Glucose,
+    /// Caramelizes from ~110°C
+    Fructose,
+}
+
+impl Sugar {
+    /// Kinetic parameters, anchored ~15°C above each sugar's onset temperature
+    pub fn kinetics(self) -> ArrheniusKinetics {
+        let (activation_energy, reference_temp_c) = match self {
+            Sugar::Sucrose => (150_000.0, 175.0),
+            Sugar::Glucose => (150_000.0, 175.0),
+            Sugar::Fructose => (130_000.0, 125.0),
+        };
+        ArrheniusKinetics {
+            activation_energy,
+            reference_temp_c,
+            reference_rate: 0.005,
+        }
+    }
+}
+
+/// Caramelization parameters for one food surface
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaramelizationModel {
+    /// `(sugar, share of total sugar)`
+    pub sugars: Vec<(Sugar, f64)>,
+    /// Total sugar content, g/100g
+    pub sugar_pct: f64,
+}
+
+impl CaramelizationModel {
+    pub fn new(sugars: Vec<(Sugar, f64)>, sugar_pct: f64) -> Self {
+        Self { sugars, sugar_pct }
+    }
+
+    /// Build from a SID composition. SID does not break sugars down, so the
+    /// total is treated as sucrose.
+    pub fn from_composition(composition: &Composition) -> Self {
+        Self::new(vec![(Sugar::Sucrose, 1.0)], composition.sugar)
+    }
+
+    /// Browning index accumulated per second
+    pub fn rate(&self, temperature_c: f64) -> f64 {
+        let sugar = self.sugar_pct.max(0.0);
+        let availability = sugar / (sugar + 5.0);
+        self.sugars
+            .iter()
+            .map(|(s, share)| share * s.kinetics().rate(temperature_c))
+            .sum::<f64>()
+            * availability
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Combined state
+// ---------------------------------------------------------------------------
+
+/// Accumulated surface browning
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrowningState {
+    pub maillard: MaillardModel,
+    pub caramelization: CaramelizationModel,
+    maillard_index: f64,
+    caramel_index: f64,
+}
+
+impl BrowningState {
+    pub fn new(maillard: MaillardModel, caramelization: CaramelizationModel) -> Self {
+        Self {
+            maillard,
+            caramelization,
+            maillard_index: 0.0,
+            caramel_index: 0.0,
+        }
+    }
+
+    /// Unbrowned surface of a SID ingredient
+    pub fn for_composition(composition: &Composition) -> Self {
+        Self::new(
+            MaillardModel::from_composition(composition),
+            CaramelizationModel::from_composition(composition),
+        )
+    }
+
+    /// Hold the surface at `temperature_c` and `water_activity` for `dt` seconds
+    pub fn advance(&mut self, temperature_c: f64, water_activity: f64, dt: f64) {
+        self.maillard_index += self.maillard.rate(temperature_c, water_activity) * dt;
+        self.caramel_index += self.caramelization.rate(temperature_c) * dt;
+    }
+
+    /// Integrate over `(time_s, temperature_c, water_activity)` samples
+    pub fn integrate(&mut self, history: &[(f64, f64, f64)]) {
+        for pair in history.windows(2) {
+            let (t0, temp0, aw0) = pair[0];
+            let (t1, temp1, aw1) = pair[1];
+            self.advance((temp0 + temp1) / 2.0, (aw0 + aw1) / 2.0, t1 - t0);
+        }
+    }
+
+    /// Browning index contributed by the Maillard reaction
+    pub fn maillard_index(&self) -> f64 {
+        self.maillard_index
+    }
+
+    /// Browning index contributed by caramelization
+    pub fn caramelization_index(&self) -> f64 {
+        self.caramel_index
+    }
+
+    /// Combined browning index
+    pub fn index(&self) -> f64 {
+        self.maillard_index + self.caramel_index
+    }
+
+    /// Visible browning level
+    pub fn level(&self) -> BrowningLevel {
+        browning_level(self.index())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn composition(protein: f64, sugar: f64, ph: Option<f64>) -> Composition {
+        Composition {
+            water: 60.0,
+            protein,
+            total_fat: 10.0,
+            saturated_fat: 4.0,
+            carbohydrates: sugar,
+            fiber: 0.0,
+            sugar,
+            ph,
+            minerals: Default::default(),
+            vitamins: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_no_hard_cutoff_but_negligible_when_cool() {
+        let model = MaillardModel::new(5.0, 10.0, 6.0);
+        let at_100 = model.rate(100.0, 0.7);
+        let at_150 = model.rate(150.0, 0.7);
+        assert!(at_100 > 0.0);
+        assert!(at_150 > 50.0 * at_100);
+    }
+
+    #[test]
+    fn test_water_activity_peak() {
+        let model = MaillardModel::new(5.0, 10.0, 6.0);
+        assert!(model.rate(150.0, 0.7) > model.rate(150.0, 0.4));
+        assert!(model.rate(150.0, 0.7) > 100.0 * model.rate(150.0, 1.0));
+    }
+
+    #[test]
+    fn test_alkaline_browns_faster() {
+        let acidic = MaillardModel::new(2.0, 10.0, 4.5);
+        let alkaline = MaillardModel::new(2.0, 10.0, 8.5);
+        assert!(alkaline.rate(150.0, 0.7) > acidic.rate(150.0, 0.7));
+    }
+
+    #[test]
+    fn test_seared_steak_is_dark() {
+        let mut crust = BrowningState::for_composition(&composition(19.0, 0.0, Some(5.6)));
+        crust.integrate(&[(0.0, 160.0, 0.7), (240.0, 160.0, 0.7)]);
+        assert_eq!(crust.level(), BrowningLevel::Dark);
+    }
+
+    #[test]
+    fn test_fried_egg_is_light() {
+        let mut white = BrowningState::for_composition(&composition(12.56, 0.37, Some(7.6)));
+        white.integrate(&[(0.0, 140.0, 0.85), (180.0, 140.0, 0.85)]);
+        assert_eq!(white.level(), BrowningLevel::Light);
+    }
+
+    #[test]
+    fn test_fructose_caramelizes_before_sucrose() {
+        let sucrose = CaramelizationModel::new(vec![(Sugar::Sucrose, 1.0)], 100.0);
+        let fructose = CaramelizationModel::new(vec![(Sugar::Fructose, 1.0)], 100.0);
+        assert!(fructose.rate(130.0) > 10.0 * sucrose.rate(130.0));
+    }
+
+    #[test]
+    fn test_dry_sugar_burns() {
+        let mut sugar = BrowningState::new(
+            MaillardModel::new(0.0, 0.0, 6.0),
+            CaramelizationModel::new(vec![(Sugar::Sucrose, 1.0)], 100.0),
+        );
+        sugar.advance(180.0, 0.2, 60.0);
+        assert!(matches!(
+            sugar.level(),
+            BrowningLevel::Medium | BrowningLevel::Dark
+        ));
+        sugar.advance(220.0, 0.2, 300.0);
+        assert_eq!(sugar.level(), BrowningLevel::Burnt);
+    }
+
+    #[test]
+    fn test_surface_water_activity() {
+        assert!(surface_water_activity(76.0) > 0.99);
+        assert!(surface_water_activity(10.0) < 0.6);
+        assert_eq!(surface_water_activity(0.0), 0.0);
+    }
+}
@@ -0,0 +1,674 @@
+//! # 1-D Heat Conduction
+//!
+//! Finite-difference solver for transient conduction inside thick foods.
+//! The lumped `heat_transfer` step assumes a single uniform temperature,
+//! which cannot predict the core of a 2.5 cm steak. This module solves
+//!
+//! ```text
+//! ρ c ∂T/∂t = (1 / r^m) ∂/∂r (k r^m ∂T/∂r)     m = 0 slab, 1 cylinder, 2 sphere
+//! ```
+//!
+//! with a finite-volume discretization and implicit (backward Euler) time
+//! stepping, so any positive `dt` is stable.
+//!
+//! Thermal properties come from the SID composition via the Choi–Okos (1986)
+//! correlations; thickness comes from the ingredient's `thickness:` param.
+
+use crate::integrator::{check_step_size, IntegrationError};
+use crate::units::Quantity;
+use saffron_ast::Param;
+use saffron_sid::{Composition, IngredientEntry};
+use serde::{Deserialize, Serialize};
+
+/// Shape of the food body being simulated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Geometry {
+    /// Infinite slab heated through its two faces (steak, fillet)
+    Slab,
+    /// Infinite cylinder heated radially (sausage, spaghetti)
+    Cylinder,
+    /// Sphere heated radially (egg, meatball)
+    Sphere,
+}
+
+impl Geometry {
+    /// Exponent `m` of the radial term in the heat equation
+    fn shape_factor(self) -> i32 {
+        match self {
+            Geometry::Slab => 0,
+            Geometry::Cylinder => 1,
+            Geometry::Sphere => 2,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Thermal properties
+// ---------------------------------------------------------------------------
+
+/// Bulk thermal properties of a food
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ThermalProperties {
+    pub conductivity: f64,  // W/(m*K)
+    pub density: f64,       // kg/m^3
+    pub specific_heat: f64, // J/(kg*K)
+}
+
+/// Choi–Okos polynomial coefficients `(a, b, c)` for `a + bT + cT²`
+type Poly = (f64, f64, f64);
+
+struct ComponentCoefficients {
+    conductivity: Poly,
+    density: Poly,
+    specific_heat: Poly, // kJ/(kg*K)
+}
+
+// Order: water, protein, fat, carbohydrate, fiber, ash
+const CHOI_OKOS: [ComponentCoefficients; 6] = [
+    ComponentCoefficients {
+        conductivity: (0.57109, 1.7625e-3, -6.7036e-6),
+        density: (997.18, 3.1439e-3, -3.7574e-3),
+        specific_heat: (4.1762, -9.0864e-5, 5.4731e-6),
+    },
+    ComponentCoefficients {
+        conductivity: (0.17881, 1.1958e-3, -2.7178e-6),
+        density: (1329.9, -0.5184, 0.0),
+        specific_heat: (2.0082, 1.2089e-3, -1.3129e-6),
+    },
+    ComponentCoefficients {
+        conductivity: (0.18071, -2.7604e-4, -1.7749e-7),
+        density: (925.59, -0.41757, 0.0),
+        specific_heat: (1.9842, 1.4733e-3, -4.8008e-6),
+    },
+    ComponentCoefficients {
+        conductivity: (0.20141, 1.3874e-3, -4.3312e-6),
+        density: (1599.1, -0.31046, 0.0),
+        specific_heat: (1.5488, 1.9625e-3, -5.9399e-6),
+    },
+    ComponentCoefficients {
+        conductivity: (0.18331, 1.2497e-3, -3.1683e-6),
+        density: (1311.5, -0.36589, 0.0),
+        specific_heat: (1.8459, 1.8306e-3, -4.6509e-6),
+    },
+    ComponentCoefficients {
+        conductivity: (0.32962, 1.4011e-3, -2.9069e-6),
+        density: (2423.8, -0.28063, 0.0),
+        specific_heat: (1.0926, 1.8896e-3, -3.6817e-6),
+    },
+];
+
+fn eval(p: Poly, t: f64) -> f64 {
+    p.0 + p.1 * t + p.2 * t * t
+}
+
+impl ThermalProperties {
+    /// Thermal diffusivity α = k / (ρ c) in m^2/s
+    pub fn diffusivity(&self) -> f64 {
+        self.conductivity / (self.density * self.specific_heat)
+    }
+
+    /// Estimate properties from a SID composition using Choi–Okos at `temperature_c`.
+    ///
+    /// USDA carbohydrates include fiber, so fiber is subtracted before weighting.
+    /// Whatever mass is left over is treated as ash (minerals).
+    pub fn from_composition(composition: &Composition, temperature_c: f64) -> Self {
+        let carbs = (composition.carbohydrates - composition.fiber).max(0.0);
+        let mut fractions = [
+            composition.water.max(0.0),
+            composition.protein.max(0.0),
+            composition.total_fat.max(0.0),
+            carbs,
+            composition.fiber.max(0.0),
+            0.0,
+        ];
+        let named: f64 = fractions.iter().sum();
+        fractions[5] = (100.0 - named).max(0.0);
+        let total: f64 = fractions.iter().sum();
+
+        let t = temperature_c;
+        let mut inv_density = 0.0;
+        let mut specific_heat = 0.0;
+        for (x, coeff) in fractions.iter().zip(CHOI_OKOS.iter()) {
+            let x = x / total;
+            inv_density += x / eval(coeff.density, t);
+            specific_heat += x * eval(coeff.specific_heat, t) * 1000.0;
+        }
+        let density = 1.0 / inv_density;
+
+        // Parallel model weighted by volume fraction
+        let mut conductivity = 0.0;
+        for (x, coeff) in fractions.iter().zip(CHOI_OKOS.iter()) {
+            let volume_fraction = (x / total) / eval(coeff.density, t) * density;
+            conductivity += volume_fraction * eval(coeff.conductivity, t);
+        }
+
+        Self {
+            conductivity,
+            density,
+            specific_heat,
+        }
+    }
+
+    /// Properties for a SID entry at `temperature_c`.
+    ///
+    /// Measured `density_g_per_ml` and `specific_heat_j_per_g_k` take precedence
+    /// over the composition estimate when present.
+    pub fn from_sid(entry: &IngredientEntry, temperature_c: f64) -> Self {
+        let mut props = Self::from_composition(&entry.composition, temperature_c);
+        if let Some(density) = entry.physical.density_g_per_ml {
+            props.density = density * 1000.0;
+        }
+        if let Some(cp) = entry.physical.specific_heat_j_per_g_k {
+            props.specific_heat = cp * 1000.0;
+        }
+        props
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Boundary conditions
+// ---------------------------------------------------------------------------
+
+/// Heat exchange at a food surface
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BoundaryCondition {
+    /// Zero flux (insulated face or symmetry plane)
+    Insulated,
+    /// Newton exchange q = h (T_ambient - T_surface)
+    Convective {
+        ambient_c: f64,
+        h: f64, // W/(m^2*K)
+    },
+    /// Surface pinned to a fixed temperature
+    FixedTemperature(f64),
+}
+
+impl BoundaryCondition {
+    /// Contact with a hot pan or grill grate (contact conductance ~500 W/m²K)
+    pub fn pan_contact(surface_c: f64) -> Self {
+        BoundaryCondition::Convective {
+            ambient_c: surface_c,
+            h: 500.0,
+        }
+    }
+
+    /// Still air, natural convection (~10 W/m²K)
+    pub fn still_air(ambient_c: f64) -> Self {
+        BoundaryCondition::Convective { ambient_c, h: 10.0 }
+    }
+
+    /// Forced air in a convection oven (~25 W/m²K)
+    pub fn forced_air(ambient_c: f64) -> Self {
+        BoundaryCondition::Convective { ambient_c, h: 25.0 }
+    }
+
+    /// Immersion in boiling water (~1500 W/m²K)
+    pub fn boiling_water(boiling_point_c: f64) -> Self {
+        BoundaryCondition::Convective {
+            ambient_c: boiling_point_c,
+            h: 1500.0,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Solver
+// ---------------------------------------------------------------------------
+
+/// Temperature distribution across the body at one instant
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemperatureProfile {
+    pub time_s: f64,
+    /// Node positions in meters (distance from the lower face, or from the center for radial shapes)
+    pub positions_m: Vec<f64>,
+    pub temperatures_c: Vec<f64>,
+    pub geometry: Geometry,
+}
+
+impl TemperatureProfile {
+    /// Temperature at the geometric center (where a probe thermometer sits)
+    pub fn core_c(&self) -> f64 {
+        match self.geometry {
+            Geometry::Slab => {
+                let n = self.temperatures_c.len();
+                if n % 2 == 1 {
+                    self.temperatures_c[n / 2]
+                } else {
+                    (self.temperatures_c[n / 2 - 1] + self.temperatures_c[n / 2]) / 2.0
+                }
+            }
+            Geometry::Cylinder | Geometry::Sphere => self.temperatures_c[0],
+        }
+    }
+
+    /// Temperature of the outer (upper) surface
+    pub fn surface_c(&self) -> f64 {
+        *self.temperatures_c.last().unwrap_or(&f64::NAN)
+    }
+
+    /// Coldest point in the body
+    pub fn min_c(&self) -> f64 {
+        self.temperatures_c
+            .iter()
+            .copied()
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Hottest point in the body
+    pub fn max_c(&self) -> f64 {
+        self.temperatures_c
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max)
+    }
+}
+
+/// Implicit finite-volume solver for 1-D transient conduction.
+///
+/// For a slab, `lower` is the face at x = 0 (e.g. against the pan) and
+/// `upper` the face at x = thickness. For cylinders and spheres the domain
+/// runs from the center (symmetry, `lower` ignored) to the outer surface.
+#[derive(Debug, Clone)]
+pub struct ConductionSolver {
+    geometry: Geometry,
+    props: ThermalProperties,
+    positions: Vec<f64>,
+    /// ρc-weighted control volume per node (per unit area/angle)
+    volumes: Vec<f64>,
+    /// Conductance between node i and i+1 (k A / dx)
+    conductances: Vec<f64>,
+    /// Exposed area at the lower and upper boundary nodes
+    boundary_areas: (f64, f64),
+    temperatures: Vec<f64>,
+    time_s: f64,
+    lower: BoundaryCondition,
+    upper: BoundaryCondition,
+}
+
+impl ConductionSolver {
+    /// Create a solver with a uniform initial temperature.
+    ///
+    /// `thickness_m` is the full slab thickness, or the diameter for cylinders
+    /// and spheres. `nodes` is clamped to at least 3.
+    pub fn new(
+        geometry: Geometry,
+        thickness_m: f64,
+        props: ThermalProperties,
+        nodes: usize,
+        initial_c: f64,
+    ) -> Self {
+        let nodes = nodes.max(3);
+        let length = match geometry {
+            Geometry::Slab => thickness_m,
+            Geometry::Cylinder | Geometry::Sphere => thickness_m / 2.0,
+        };
+        let dx = length / (nodes - 1) as f64;
+        let m = geometry.shape_factor();
+        let area = |r: f64| r.powi(m);
+        let shell = |r0: f64, r1: f64| (r1.powi(m + 1) - r0.powi(m + 1)) / (m + 1) as f64;
+
+        let positions: Vec<f64> = (0..nodes).map(|i| i as f64 * dx).collect();
+        let volumes = positions
+            .iter()
+            .map(|&r| {
+                let r0 = (r - dx / 2.0).max(0.0);
+                let r1 = (r + dx / 2.0).min(length);
+                shell(r0, r1) * props.density * props.specific_heat
+            })
+            .collect();
+        let conductances = (0..nodes - 1)
+            .map(|i| props.conductivity * area(positions[i] + dx / 2.0) / dx)
+            .collect();
+
+        Self {
+            geometry,
+            props,
+            positions,
+            volumes,
+            conductances,
+            boundary_areas: (area(0.0), area(length)),
+            temperatures: vec![initial_c; nodes],
+            time_s: 0.0,
+            lower: BoundaryCondition::Insulated,
+            upper: BoundaryCondition::Insulated,
+        }
+    }
+
+    /// Create a solver for a SID ingredient, with properties evaluated at `initial_c`
+    pub fn from_sid(
+        entry: &IngredientEntry,
+        geometry: Geometry,
+        thickness_m: f64,
+        nodes: usize,
+        initial_c: f64,
+    ) -> Self {
+        let props = ThermalProperties::from_sid(entry, initial_c);
+        Self::new(geometry, thickness_m, props, nodes, initial_c)
+    }
+
+    /// Set both boundary conditions
+    pub fn with_boundaries(mut self, lower: BoundaryCondition, upper: BoundaryCondition) -> Self {
+        self.set_boundaries(lower, upper);
+        self
+    }
+
+    /// Change boundary conditions mid-simulation (e.g. flipping a steak)
+    pub fn set_boundaries(&mut self, lower: BoundaryCondition, upper: BoundaryCondition) {
+        self.lower = lower;
+        self.upper = upper;
+    }
+
+    /// Current boundary conditions as `(lower, upper)`
+    pub fn boundaries(&self) -> (BoundaryCondition, BoundaryCondition) {
+        (self.lower, self.upper)
+    }
+
+    /// Thermal properties used by the solver
+    pub fn properties(&self) -> &ThermalProperties {
+        &self.props
+    }
+
+    /// Elapsed simulated time in seconds
+    pub fn time_s(&self) -> f64 {
+        self.time_s
+    }
+
+    /// Snapshot of the current temperature field
+    pub fn profile(&self) -> TemperatureProfile {
+        TemperatureProfile {
+            time_s: self.time_s,
+            positions_m: self.positions.clone(),
+            temperatures_c: self.temperatures.clone(),
+            geometry: self.geometry,
+        }
+    }
+
+    /// Advance one implicit step of `dt` seconds
+    pub fn step(&mut self, dt: f64) -> Result<(), IntegrationError> {
+        check_step_size(dt)?;
+        let n = self.temperatures.len();
+        let mut lower = vec![0.0; n];
+        let mut diag = vec![0.0; n];
+        let mut upper = vec![0.0; n];
+        let mut rhs = vec![0.0; n];
+
+        for i in 0..n {
+            let capacity = self.volumes[i] / dt;
+            diag[i] = capacity;
+            rhs[i] = capacity * self.temperatures[i];
+            if i > 0 {
+                let g = self.conductances[i - 1];
+                lower[i] = -g;
+                diag[i] += g;
+            }
+            if i + 1 < n {
+                let g = self.conductances[i];
+                upper[i] = -g;
+                diag[i] += g;
+            }
+        }
+
+        let radial = self.geometry != Geometry::Slab;
+        let boundaries = [
+            (
+                0,
+                if radial {
+                    BoundaryCondition::Insulated
+                } else {
+                    self.lower
+                },
+                self.boundary_areas.0,
+            ),
+            (n - 1, self.upper, self.boundary_areas.1),
+        ];
+        for (i, bc, area) in boundaries {
+            match bc {
+                BoundaryCondition::Insulated => {}
+                BoundaryCondition::Convective { ambient_c, h } => {
+                    diag[i] += h * area;
+                    rhs[i] += h * area * ambient_c;
+                }
+                BoundaryCondition::FixedTemperature(t) => {
+                    lower[i] = 0.0;
+                    upper[i] = 0.0;
+                    diag[i] = 1.0;
+                    rhs[i] = t;
+                }
+            }
+        }
+
+        self.temperatures = solve_tridiagonal(&lower, &diag, &upper, &rhs);
+        self.time_s += dt;
+        Ok(())
+    }
+
+    /// Run for `duration_s`, returning a profile after every step (plus the initial state)
+    pub fn simulate(
+        &mut self,
+        duration_s: f64,
+        dt: f64,
+    ) -> Result<Vec<TemperatureProfile>, IntegrationError> {
+        check_step_size(dt)?;
+        let mut history = vec![self.profile()];
+        let end = self.time_s + duration_s;
+        while self.time_s < end - 1e-9 {
+            self.step(dt.min(end - self.time_s))?;
+            history.push(self.profile());
+        }
+        Ok(history)
+    }
+
+    /// Step until the core reaches `target_c`; returns the elapsed time or `None` after `max_duration_s`
+    pub fn time_to_core(
+        &mut self,
+        target_c: f64,
+        dt: f64,
+        max_duration_s: f64,
+    ) -> Result<Option<f64>, IntegrationError> {
+        check_step_size(dt)?;
+        let start = self.time_s;
+        let heating = self.profile().core_c() <= target_c;
+        while self.time_s - start < max_duration_s {
+            let core = self.profile().core_c();
+            if (heating && core >= target_c) || (!heating && core <= target_c) {
+                return Ok(Some(self.time_s - start));
+            }
+            self.step(dt)?;
+        }
+        Ok(None)
+    }
+}
+
+/// Thomas algorithm for a tridiagonal system
+fn solve_tridiagonal(lower: &[f64], diag: &[f64], upper: &[f64], rhs: &[f64]) -> Vec<f64> {
+    let n = diag.len();
+    let mut c = vec![0.0; n];
+    let mut d = vec![0.0; n];
+    c[0] = upper[0] / diag[0];
+    d[0] = rhs[0] / diag[0];
+    for i in 1..n {
+        let denom = diag[i] - lower[i] * c[i - 1];
+        c[i] = upper[i] / denom;
+        d[i] = (rhs[i] - lower[i] * d[i - 1]) / denom;
+    }
+    let mut x = vec![0.0; n];
+    x[n - 1] = d[n - 1];
+    for i in (0..n - 1).rev() {
+        x[i] = d[i] - c[i] * x[i + 1];
+    }
+    x
+}
+
+/// Read a `thickness:` (or `diameter:`) param from an ingredient declaration, in meters
+pub fn thickness_from_params(params: &[Param]) -> Option<f64> {
+    params
+        .iter()
+        .find(|p| p.name == "thickness" || p.name == "diameter")
+        .and_then(|p| match Quantity::from_expr(&p.value)? {
+            Quantity::Length(length) => Some(length.si()),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use saffron_ast::{Expr, Span, Unit};
+
+    fn beef_composition() -> Composition {
+        Composition {
+            water: 60.0,
+            protein: 19.0,
+            total_fat: 20.0,
+            saturated_fat: 8.0,
+            carbohydrates: 0.0,
+            fiber: 0.0,
+            sugar: 0.0,
+            ph: Some(5.6),
+            minerals: Default::default(),
+            vitamins: Default::default(),
+        }
+    }
+
+    fn span() -> Span {
+        Span {
+            file: "test.saffron".into(),
+            start_line: 1,
+            start_col: 1,
+            end_line: 1,
+            end_col: 1,
+            byte_offset: 0,
+            byte_length: 0,
+        }
+    }
+
+    #[test]
+    fn test_water_properties() {
+        let water = Composition {
+            water: 100.0,
+            protein: 0.0,
+            total_fat: 0.0,
+            ..beef_composition()
+        };
+        let props = ThermalProperties::from_composition(&water, 20.0);
+        assert!((props.conductivity - 0.6).abs() < 0.02);
+        assert!((props.density - 997.0).abs() < 5.0);
+        assert!((props.specific_heat - 4180.0).abs() < 20.0);
+    }
+
+    #[test]
+    fn test_beef_properties_plausible() {
+        let props = ThermalProperties::from_composition(&beef_composition(), 20.0);
+        assert!(props.conductivity > 0.4 && props.conductivity < 0.5);
+        assert!(props.density > 1000.0 && props.density < 1100.0);
+        assert!(props.diffusivity() > 1.0e-7 && props.diffusivity() < 1.6e-7);
+    }
+
+    #[test]
+    fn test_slab_matches_analytic_center() {
+        // Slab with both faces held at 100C, initially 0C.
+        // Series solution at the center, first three terms.
+        let props = ThermalProperties {
+            conductivity: 0.5,
+            density: 1000.0,
+            specific_heat: 4000.0,
+        };
+        let l = 0.02;
+        let mut solver = ConductionSolver::new(Geometry::Slab, l, props, 41, 0.0).with_boundaries(
+            BoundaryCondition::FixedTemperature(100.0),
+            BoundaryCondition::FixedTemperature(100.0),
+        );
+        let t = 300.0;
+        solver.simulate(t, 0.5).unwrap();
+
+        let alpha = props.diffusivity();
+        let half = l / 2.0;
+        let mut theta = 0.0;
+        for n in 0..3 {
+            let lambda = (2 * n + 1) as f64 * std::f64::consts::PI / 2.0;
+            let sign = if n % 2 == 0 { 1.0 } else { -1.0 };
+            theta += sign * 4.0 / ((2 * n + 1) as f64 * std::f64::consts::PI)
+                * (-lambda * lambda * alpha * t / (half * half)).exp();
+        }
+        let expected = 100.0 * (1.0 - theta);
+        let core = solver.profile().core_c();
+        assert!(
+            (core - expected).abs() < 1.0,
+            "core {core} vs analytic {expected}"
+        );
+    }
+
+    #[test]
+    fn test_steak_core_lags_surface() {
+        let props = ThermalProperties::from_composition(&beef_composition(), 5.0);
+        let mut solver = ConductionSolver::new(Geometry::Slab, 0.025, props, 26, 5.0)
+            .with_boundaries(
+                BoundaryCondition::pan_contact(230.0),
+                BoundaryCondition::still_air(22.0),
+            );
+        let history = solver.simulate(120.0, 1.0).unwrap();
+        let last = history.last().unwrap();
+        assert!(last.temperatures_c[0] > last.core_c());
+        assert!(last.core_c() >= 5.0);
+        assert!(last.max_c() < 230.0);
+    }
+
+    #[test]
+    fn test_sphere_heats_faster_than_slab() {
+        let props = ThermalProperties::from_composition(&beef_composition(), 20.0);
+        let boil = BoundaryCondition::boiling_water(100.0);
+        let mut sphere = ConductionSolver::new(Geometry::Sphere, 0.04, props, 21, 20.0)
+            .with_boundaries(BoundaryCondition::Insulated, boil);
+        let mut slab = ConductionSolver::new(Geometry::Slab, 0.04, props, 21, 20.0)
+            .with_boundaries(boil, boil);
+        let t_sphere = sphere.time_to_core(70.0, 1.0, 7200.0).unwrap().unwrap();
+        let t_slab = slab.time_to_core(70.0, 1.0, 7200.0).unwrap().unwrap();
+        assert!(t_sphere < t_slab);
+    }
+
+    #[test]
+    fn test_insulated_conserves_temperature() {
+        let props = ThermalProperties::from_composition(&beef_composition(), 20.0);
+        let mut solver = ConductionSolver::new(Geometry::Cylinder, 0.03, props, 11, 42.0);
+        solver.simulate(600.0, 10.0).unwrap();
+        for t in solver.profile().temperatures_c {
+            assert!((t - 42.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_thickness_from_params() {
+        let params = vec![Param {
+            name: "thickness".into(),
+            value: Expr::UnitLiteral {
+                value: 2.5,
+                unit: Unit::Centimeters,
+                span: span(),
+            },
+            span: span(),
+        }];
+        assert!((thickness_from_params(&params).unwrap() - 0.025).abs() < 1e-12);
+        assert_eq!(thickness_from_params(&[]), None);
+    }
+
+    #[test]
+    fn test_rejects_non_positive_step() {
+        let props = ThermalProperties::from_composition(&beef_composition(), 20.0);
+        let mut solver = ConductionSolver::new(Geometry::Slab, 0.02, props, 11, 20.0);
+        for dt in [0.0, -1.0] {
+            assert_eq!(
+                solver.step(dt),
+                Err(IntegrationError::InvalidStepSize { dt })
+            );
+            assert!(matches!(
+                solver.simulate(60.0, dt),
+                Err(IntegrationError::InvalidStepSize { .. })
+            ));
+            assert!(matches!(
+                solver.time_to_core(70.0, dt, 600.0),
+                Err(IntegrationError::InvalidStepSize { .. })
+            ));
+        }
+        assert_eq!(solver.time_s(), 0.0);
+    }
+}
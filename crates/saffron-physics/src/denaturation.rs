@@ -0,0 +1,478 @@
+//! # Protein Denaturation
+//!
+//! First-order kinetic model of protein unfolding over a temperature history.
+//! Each protein fraction denatures at its own Arrhenius rate
+//!
+//! ```text
+//! dN/dt = -k(T) N,    k(T) = k_ref · exp(-Ea/R · (1/T - 1/T_ref))
+//! ```
+//!
+//! where `N` is the native (undenatured) fraction. The denatured fractions of
+//! myosin/actin map onto `Doneness`, and those of the egg proteins onto
+//! `TextureState`.
+//!
+//! Kinetic parameters come from the SID entry's `denaturation` profiles; the
+//! built-in tables below are the fallback for entries that have none.
+
+use saffron_ast::{Doneness, TextureState};
+use saffron_sid::{DenaturationProfile, IngredientEntry};
+use serde::{Deserialize, Serialize};
+
+/// Universal gas constant, J/(mol*K)
+const GAS_CONSTANT: f64 = 8.314;
+
+/// Arrhenius rate law anchored at a reference temperature
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ArrheniusKinetics {
+    pub activation_energy: f64, // J/mol
+    pub reference_temp_c: f64,
+    pub reference_rate: f64, // 1/s at reference_temp_c
+}
+
+impl ArrheniusKinetics {
+    /// Rate constant at `temperature_c`, in 1/s
+    pub fn rate(&self, temperature_c: f64) -> f64 {
+        let t = temperature_c + 273.15;
+        let t_ref = self.reference_temp_c + 273.15;
+        self.reference_rate
+            * (-self.activation_energy / GAS_CONSTANT * (1.0 / t - 1.0 / t_ref)).exp()
+    }
+}
+
+/// Half-life of `secs` expressed as a first-order rate constant
+const fn half_life(secs: f64) -> f64 {
+    std::f64::consts::LN_2 / secs
+}
+
+/// Protein fractions with distinct denaturation behavior
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ProteinFraction {
+    /// Muscle myosin, unfolds around 50–55°C (meat turns opaque)
+    Myosin,
+    /// Muscle actin, unfolds around 66–73°C (meat turns dry and firm)
+    Actin,
+    /// Connective-tissue collagen, shrinks around 60–65°C
+    Collagen,
+    /// Main egg-white protein, sets around 80°C
+    Ovalbumin,
+    /// Egg-white ovotransferrin, the first to set, around 62°C
+    Conalbumin,
+    /// Yolk low-density lipoproteins, gel around 65–70°C
+    YolkLipoprotein,
+}
+
+impl ProteinFraction {
+    /// SID name of the fraction, e.g. `yolk_lipoprotein`
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "myosin" => ProteinFraction::Myosin,
+            "actin" => ProteinFraction::Actin,
+            "collagen" => ProteinFraction::Collagen,
+            "ovalbumin" => ProteinFraction::Ovalbumin,
+            "conalbumin" => ProteinFraction::Conalbumin,
+            "yolk_lipoprotein" => ProteinFraction::YolkLipoprotein,
+            _ => return None,
+        })
+    }
+
+    /// Fallback kinetic parameters, tuned so each fraction unfolds near its DSC peak temperature
+    pub fn kinetics(self) -> ArrheniusKinetics {
+        let (activation_energy, reference_temp_c, reference_rate) = match self {
+            ProteinFraction::Myosin => (300_000.0, 52.0, half_life(120.0)),
+            ProteinFraction::Actin => (300_000.0, 68.0, half_life(120.0)),
+            ProteinFraction::Collagen => (400_000.0, 62.0, half_life(300.0)),
+            ProteinFraction::Ovalbumin => (350_000.0, 80.0, half_life(60.0)),
+            ProteinFraction::Conalbumin => (350_000.0, 62.0, half_life(60.0)),
+            ProteinFraction::YolkLipoprotein => (350_000.0, 68.0, half_life(120.0)),
+        };
+        ArrheniusKinetics {
+            activation_energy,
+            reference_temp_c,
+            reference_rate,
+        }
+    }
+}
+
+/// What kind of protein matrix a profile describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProteinSystem {
+    Meat,
+    EggWhite,
+    EggYolk,
+}
+
+impl ProteinSystem {
+    /// SID name of the system: `meat`, `egg_white` or `egg_yolk`
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "meat" => ProteinSystem::Meat,
+            "egg_white" => ProteinSystem::EggWhite,
+            "egg_yolk" => ProteinSystem::EggYolk,
+            _ => return None,
+        })
+    }
+}
+
+/// One protein fraction of a profile
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ProteinComponent {
+    pub fraction: ProteinFraction,
+    /// Share of total protein
+    pub share: f64,
+    pub kinetics: ArrheniusKinetics,
+}
+
+impl ProteinComponent {
+    /// Component with the fallback kinetics of `fraction`
+    pub fn new(fraction: ProteinFraction, share: f64) -> Self {
+        Self {
+            fraction,
+            share,
+            kinetics: fraction.kinetics(),
+        }
+    }
+}
+
+/// Weighted set of protein fractions for one food
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProteinProfile {
+    pub system: ProteinSystem,
+    pub fractions: Vec<ProteinComponent>,
+}
+
+/// SID subcategories whose protein behaves like skeletal muscle
+const MEAT_SUBCATEGORIES: &[&str] = &["poultry", "beef", "pork", "lamb", "veal", "game", "meat"];
+
+impl ProteinProfile {
+    /// Skeletal muscle: myofibrillar proteins plus connective tissue
+    pub fn meat() -> Self {
+        Self {
+            system: ProteinSystem::Meat,
+            fractions: vec![
+                ProteinComponent::new(ProteinFraction::Myosin, 0.55),
+                ProteinComponent::new(ProteinFraction::Actin, 0.25),
+                ProteinComponent::new(ProteinFraction::Collagen, 0.20),
+            ],
+        }
+    }
+
+    /// Egg white (albumen)
+    pub fn egg_white() -> Self {
+        Self {
+            system: ProteinSystem::EggWhite,
+            fractions: vec![
+                ProteinComponent::new(ProteinFraction::Ovalbumin, 0.54),
+                ProteinComponent::new(ProteinFraction::Conalbumin, 0.12),
+            ],
+        }
+    }
+
+    /// Egg yolk
+    pub fn egg_yolk() -> Self {
+        Self {
+            system: ProteinSystem::EggYolk,
+            fractions: vec![ProteinComponent::new(ProteinFraction::YolkLipoprotein, 1.0)],
+        }
+    }
+
+    /// Profile from a SID `denaturation` entry; `None` if it names an unknown system or protein
+    pub fn from_sid(profile: &DenaturationProfile) -> Option<Self> {
+        let fractions = profile
+            .fractions
+            .iter()
+            .map(|f| {
+                Some(ProteinComponent {
+                    fraction: ProteinFraction::from_name(&f.protein)?,
+                    share: f.share,
+                    kinetics: ArrheniusKinetics {
+                        activation_energy: f.activation_energy_j_per_mol,
+                        reference_temp_c: f.reference_temp_c,
+                        reference_rate: f.reference_rate_per_s,
+                    },
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            system: ProteinSystem::from_name(&profile.system)?,
+            fractions,
+        })
+    }
+
+    /// Profile for a SID ingredient.
+    ///
+    /// `part` selects the yolk or white of an egg (`"yolk"`, `"white"`), matching
+    /// the bindings produced by `Crack(egg) -> [yolk, white]`. The entry's own
+    /// `denaturation` profile for that part is used when present; otherwise eggs
+    /// and meats fall back to the built-in tables. Returns `None` for
+    /// ingredients without a known protein model.
+    pub fn for_ingredient(entry: &IngredientEntry, part: Option<&str>) -> Option<Self> {
+        let declared = entry
+            .denaturation
+            .iter()
+            .find(|p| p.part.as_deref() == part)
+            // A bare egg means its white, as in the fallback below
+            .or_else(|| {
+                part.is_none()
+                    .then(|| {
+                        entry
+                            .denaturation
+                            .iter()
+                            .find(|p| p.part.as_deref() == Some("white"))
+                    })
+                    .flatten()
+            });
+        if let Some(profile) = declared.and_then(Self::from_sid) {
+            return Some(profile);
+        }
+
+        let subcategory = entry.subcategory.as_deref().unwrap_or("");
+        if subcategory == "egg" {
+            return Some(match part {
+                Some("yolk") => Self::egg_yolk(),
+                _ => Self::egg_white(),
+            });
+        }
+        if MEAT_SUBCATEGORIES.contains(&subcategory) {
+            return Some(Self::meat());
+        }
+        None
+    }
+}
+
+/// Denaturation progress of a protein profile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DenaturationState {
+    profile: ProteinProfile,
+    /// Native (undenatured) share of each fraction, parallel to `profile.fractions`
+    native: Vec<f64>,
+}
+
+impl DenaturationState {
+    /// Start from fully native protein
+    pub fn new(profile: ProteinProfile) -> Self {
+        let native = vec![1.0; profile.fractions.len()];
+        Self { profile, native }
+    }
+
+    /// Hold at `temperature_c` for `dt` seconds.
+    ///
+    /// Uses the exact solution of the first-order ODE over the step, so it is
+    /// stable for any `dt`.
+    pub fn advance(&mut self, temperature_c: f64, dt: f64) {
+        for (component, native) in self.profile.fractions.iter().zip(self.native.iter_mut()) {
+            *native *= (-component.kinetics.rate(temperature_c) * dt).exp();
+        }
+    }
+
+    /// Integrate over a `(time_s, temperature_c)` history, using the mean temperature of each interval
+    pub fn integrate(&mut self, history: &[(f64, f64)]) {
+        for pair in history.windows(2) {
+            let (t0, temp0) = pair[0];
+            let (t1, temp1) = pair[1];
+            self.advance((temp0 + temp1) / 2.0, t1 - t0);
+        }
+    }
+
+    /// Denatured share (0.0–1.0) of one fraction, if present in the profile
+    pub fn denatured(&self, fraction: ProteinFraction) -> Option<f64> {
+        self.profile
+            .fractions
+            .iter()
+            .position(|c| c.fraction == fraction)
+            .map(|i| 1.0 - self.native[i])
+    }
+
+    /// Weighted denatured share across all fractions; 0 for a profile without protein
+    pub fn total_denatured(&self) -> f64 {
+        let total_weight: f64 = self.profile.fractions.iter().map(|c| c.share).sum();
+        if total_weight <= 0.0 {
+            return 0.0;
+        }
+        self.profile
+            .fractions
+            .iter()
+            .zip(&self.native)
+            .map(|(c, n)| c.share * (1.0 - n))
+            .sum::<f64>()
+            / total_weight
+    }
+
+    /// Doneness level for meat. `None` for non-meat profiles.
+    ///
+    /// `Charred` is a surface state and comes from the browning model, not from
+    /// protein denaturation.
+    pub fn doneness(&self) -> Option<Doneness> {
+        if self.profile.system != ProteinSystem::Meat {
+            return None;
+        }
+        let myosin = self.denatured(ProteinFraction::Myosin).unwrap_or(0.0);
+        let actin = self.denatured(ProteinFraction::Actin).unwrap_or(0.0);
+        Some(if myosin < 0.3 {
+            Doneness::Raw
+        } else if actin < 0.05 {
+            Doneness::Rare
+        } else if actin < 0.15 {
+            Doneness::MediumRare
+        } else if actin < 0.4 {
+            Doneness::Medium
+        } else if actin < 0.8 {
+            Doneness::MediumWell
+        } else {
+            Doneness::WellDone
+        })
+    }
+
+    /// Texture classification of the protein matrix
+    pub fn texture(&self) -> TextureState {
+        let d = |f| self.denatured(f).unwrap_or(0.0);
+        match self.profile.system {
+            ProteinSystem::EggWhite => {
+                if d(ProteinFraction::Conalbumin) < 0.5 {
+                    TextureState::Runny
+                } else if d(ProteinFraction::Ovalbumin) < 0.5 {
+                    TextureState::Set
+                } else if d(ProteinFraction::Ovalbumin) < 0.99 {
+                    TextureState::Firm
+                } else {
+                    TextureState::Rubbery
+                }
+            }
+            ProteinSystem::EggYolk => {
+                let lipo = d(ProteinFraction::YolkLipoprotein);
+                if lipo < 0.3 {
+                    TextureState::Runny
+                } else if lipo < 0.8 {
+                    TextureState::Jammy
+                } else {
+                    TextureState::Set
+                }
+            }
+            ProteinSystem::Meat => {
+                let myosin = d(ProteinFraction::Myosin);
+                let actin = d(ProteinFraction::Actin);
+                if myosin < 0.3 {
+                    TextureState::Raw
+                } else if actin < 0.4 {
+                    TextureState::Set
+                } else if actin < 0.8 {
+                    TextureState::Firm
+                } else {
+                    TextureState::Rubbery
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hold(profile: ProteinProfile, temperature_c: f64, secs: f64) -> DenaturationState {
+        let mut state = DenaturationState::new(profile);
+        state.integrate(&[(0.0, temperature_c), (secs, temperature_c)]);
+        state
+    }
+
+    #[test]
+    fn test_rate_matches_reference() {
+        let k = ProteinFraction::Myosin.kinetics();
+        assert!((k.rate(52.0) - half_life(120.0)).abs() < 1e-12);
+        assert!(k.rate(60.0) > k.rate(52.0));
+    }
+
+    #[test]
+    fn test_fridge_meat_stays_raw() {
+        let state = hold(ProteinProfile::meat(), 4.0, 3600.0);
+        assert_eq!(state.doneness(), Some(Doneness::Raw));
+        assert!(state.total_denatured() < 1e-3);
+    }
+
+    #[test]
+    fn test_meat_doneness_progression() {
+        let ten_min = 600.0;
+        assert_eq!(
+            hold(ProteinProfile::meat(), 52.0, ten_min).doneness(),
+            Some(Doneness::Rare)
+        );
+        assert_eq!(
+            hold(ProteinProfile::meat(), 57.0, ten_min).doneness(),
+            Some(Doneness::MediumRare)
+        );
+        assert_eq!(
+            hold(ProteinProfile::meat(), 75.0, ten_min).doneness(),
+            Some(Doneness::WellDone)
+        );
+    }
+
+    #[test]
+    fn test_fried_egg_textures() {
+        // White below ovalbumin's 80C (only conalbumin sets), yolk barely warm
+        let white = hold(ProteinProfile::egg_white(), 75.0, 180.0);
+        let yolk = hold(ProteinProfile::egg_yolk(), 55.0, 180.0);
+        assert_eq!(white.texture(), TextureState::Set);
+        assert_eq!(yolk.texture(), TextureState::Runny);
+        assert_eq!(white.doneness(), None);
+    }
+
+    #[test]
+    fn test_hard_boiled_yolk_sets() {
+        let yolk = hold(ProteinProfile::egg_yolk(), 85.0, 600.0);
+        assert_eq!(yolk.texture(), TextureState::Set);
+    }
+
+    #[test]
+    fn test_profiles_from_sid() {
+        let mut client = saffron_sid::SidClient::new();
+        client
+            .load_dir(std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../sid/data"))
+            .unwrap();
+        let egg = client.get("chicken_egg").unwrap();
+        let yolk = ProteinProfile::for_ingredient(egg, Some("yolk")).unwrap();
+        assert_eq!(yolk.system, ProteinSystem::EggYolk);
+        let white = ProteinProfile::for_ingredient(egg, None).unwrap();
+        assert_eq!(white.system, ProteinSystem::EggWhite);
+        for (sid, table) in white
+            .fractions
+            .iter()
+            .zip(ProteinProfile::egg_white().fractions)
+        {
+            assert_eq!(sid.fraction, table.fraction);
+            assert!((sid.kinetics.reference_rate - table.kinetics.reference_rate).abs() < 1e-6);
+        }
+
+        // Parameters are read from the entry, not the built-in table
+        let mut breast = client.get("chicken_breast").unwrap().clone();
+        breast.denaturation[0].fractions[0].reference_temp_c = 50.0;
+        let meat = ProteinProfile::for_ingredient(&breast, None).unwrap();
+        assert_eq!(meat.fractions[0].fraction, ProteinFraction::Myosin);
+        assert_eq!(meat.fractions[0].kinetics.reference_temp_c, 50.0);
+
+        // Entries without kinetics fall back to the tables
+        breast.denaturation.clear();
+        assert_eq!(
+            ProteinProfile::for_ingredient(&breast, None),
+            Some(ProteinProfile::meat())
+        );
+    }
+
+    #[test]
+    fn test_step_size_independent() {
+        let mut coarse = DenaturationState::new(ProteinProfile::meat());
+        coarse.advance(60.0, 300.0);
+        let mut fine = DenaturationState::new(ProteinProfile::meat());
+        for _ in 0..300 {
+            fine.advance(60.0, 1.0);
+        }
+        assert!((coarse.total_denatured() - fine.total_denatured()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty_profile_is_not_denatured() {
+        let empty = ProteinProfile {
+            system: ProteinSystem::Meat,
+            fractions: vec![],
+        };
+        assert_eq!(hold(empty, 90.0, 600.0).total_denatured(), 0.0);
+    }
+}
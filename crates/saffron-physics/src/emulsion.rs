@@ -0,0 +1,331 @@
+//! # Emulsion Stability
+//!
+//! Estimates whether an oil-in-water emulsion forms under `Whisk`, `Blend` or
+//! `Emulsify`, and how long it lasts:
+//!
+//! 1. **Droplet size** from the shear energy density, `d ∝ E_v^-0.4`.
+//! 2. **Interface coverage** — emulsifier needed is `Γ · 6 V_oil / d` with a
+//!    surface load Γ ≈ 2.5 mg/m²; when short, droplets coalesce until the
+//!    available emulsifier covers them.
+//! 3. **Creaming** — Stokes velocity of the droplets through a continuous
+//!    phase thickened by the droplets themselves (Krieger–Dougherty).
+//!
+//! Too much oil (past close packing), too little emulsifier, or a temperature
+//! that coagulates egg proteins breaks the emulsion.
+
+use saffron_ast::{Phase, ProcessType};
+use saffron_sid::IngredientEntry;
+use serde::{Deserialize, Serialize};
+
+/// Emulsifier surface load, kg/m^2
+const SURFACE_LOAD: f64 = 2.5e-6;
+
+/// Oil fraction past which droplets can no longer pack and the emulsion inverts
+const MAX_OIL_FRACTION: f64 = 0.9;
+
+/// Droplet size prefactor for `d = C · E_v^-0.4` (m, with E_v in J/m^3)
+const DROPLET_SIZE_PREFACTOR: f64 = 6.4e-3;
+
+/// Oil–water density difference, kg/m^3
+const DENSITY_DIFFERENCE: f64 = 80.0;
+
+/// Height over which creaming is judged (a small bowl), m
+const CREAMING_HEIGHT: f64 = 0.05;
+
+/// Creaming time that scores a stability of 0.5, s
+const REFERENCE_LIFETIME_S: f64 = 3600.0;
+
+/// Below this share of the sheared droplet surface covered, droplets coalesce on contact
+const MIN_COVERAGE: f64 = 0.25;
+
+/// Below this score the emulsion separates almost immediately
+const MIN_STABILITY: f64 = 0.05;
+
+/// Tool that puts shear into the mixture
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShearDevice {
+    Fork,
+    Whisk,
+    ImmersionBlender,
+    Blender,
+}
+
+impl ShearDevice {
+    /// Mechanical power dissipated per unit volume, W/m^3
+    pub fn power_density(self) -> f64 {
+        match self {
+            ShearDevice::Fork => 2.0e3,
+            ShearDevice::Whisk => 1.0e4,
+            ShearDevice::ImmersionBlender => 3.0e5,
+            ShearDevice::Blender => 1.0e6,
+        }
+    }
+
+    /// Default device for a mixing process
+    pub fn for_process(process: &ProcessType) -> Option<Self> {
+        match process {
+            ProcessType::Whisk | ProcessType::Emulsify => Some(ShearDevice::Whisk),
+            ProcessType::Blend => Some(ShearDevice::Blender),
+            _ => None,
+        }
+    }
+}
+
+/// Emulsifying material per gram of a SID ingredient.
+///
+/// Phospholipids (egg yolk lecithin) count fully; proteins adsorb too but
+/// less efficiently. `part` selects `"yolk"` or `"white"` of an egg.
+pub fn emulsifier_fraction(entry: &IngredientEntry, part: Option<&str>) -> f64 {
+    let phospholipids = match (entry.subcategory.as_deref(), part) {
+        (Some("egg"), Some("yolk")) => 0.09,
+        (Some("egg"), Some("white")) => 0.0,
+        (Some("egg"), _) => 0.03,
+        (Some("mustard"), _) => 0.01,
+        _ => 0.0,
+    };
+    let protein = match (entry.subcategory.as_deref(), part) {
+        (Some("egg"), Some("yolk")) => 0.16,
+        (Some("egg"), Some("white")) => 0.11,
+        _ => entry.composition.protein / 100.0,
+    };
+    phospholipids + 0.1 * protein
+}
+
+/// Why an emulsion failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BreakReason {
+    /// Oil fraction beyond close packing; the emulsion inverts or weeps oil
+    TooMuchOil,
+    /// Not enough emulsifier to cover any useful droplet surface
+    InsufficientEmulsifier,
+    /// Emulsifying proteins coagulated
+    TooHot,
+    /// Water phase freezing
+    TooCold,
+    /// Droplets are so large they separate within seconds
+    Creamed,
+}
+
+/// Outcome of an emulsification attempt
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum EmulsionState {
+    Stable {
+        /// Sauter mean droplet diameter, micrometers
+        droplet_diameter_um: f64,
+        /// 0.0 (separates at once) to 1.0 (indefinitely stable)
+        stability: f64,
+    },
+    Broken {
+        reason: BreakReason,
+    },
+}
+
+impl EmulsionState {
+    /// Phase of matter: `Emulsion` when stable, separated `Liquid` when broken
+    pub fn phase(&self) -> Phase {
+        match self {
+            EmulsionState::Stable { .. } => Phase::Emulsion,
+            EmulsionState::Broken { .. } => Phase::Liquid,
+        }
+    }
+
+    /// Stability score, 0.0 when broken
+    pub fn stability(&self) -> f64 {
+        match self {
+            EmulsionState::Stable { stability, .. } => *stability,
+            EmulsionState::Broken { .. } => 0.0,
+        }
+    }
+}
+
+/// Mixture and process conditions for an emulsion
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EmulsionInput {
+    pub oil_ml: f64,
+    pub water_ml: f64,
+    /// Emulsifier mass, g (see [`emulsifier_fraction`])
+    pub emulsifier_g: f64,
+    pub device: ShearDevice,
+    pub duration_s: f64,
+    pub temperature_c: f64,
+    /// Temperature at which the emulsifier fails (~65°C for egg yolk)
+    pub max_temperature_c: f64,
+}
+
+/// Viscosity of water at `temperature_c` (Vogel equation), Pa*s
+fn water_viscosity(temperature_c: f64) -> f64 {
+    2.414e-5 * 10f64.powf(247.8 / (temperature_c + 133.15))
+}
+
+impl EmulsionInput {
+    /// Oil volume fraction φ
+    pub fn oil_fraction(&self) -> f64 {
+        let total = self.oil_ml + self.water_ml;
+        if total > 0.0 {
+            self.oil_ml / total
+        } else {
+            0.0
+        }
+    }
+
+    /// Evaluate whether the emulsion forms and how stable it is
+    pub fn evaluate(&self) -> EmulsionState {
+        let phi = self.oil_fraction();
+        if self.temperature_c > self.max_temperature_c {
+            return EmulsionState::Broken {
+                reason: BreakReason::TooHot,
+            };
+        }
+        if self.temperature_c < 1.0 {
+            return EmulsionState::Broken {
+                reason: BreakReason::TooCold,
+            };
+        }
+        if phi > MAX_OIL_FRACTION {
+            return EmulsionState::Broken {
+                reason: BreakReason::TooMuchOil,
+            };
+        }
+        if self.emulsifier_g <= 0.0 {
+            return EmulsionState::Broken {
+                reason: BreakReason::InsufficientEmulsifier,
+            };
+        }
+
+        // Droplet size: shear-limited, or coverage-limited if emulsifier is short
+        let energy_density = self.device.power_density() * self.duration_s.max(1.0);
+        let shear_d = DROPLET_SIZE_PREFACTOR * energy_density.powf(-0.4);
+        let oil_m3 = self.oil_ml * 1e-6;
+        let emulsifier_kg = self.emulsifier_g / 1000.0;
+        let coverage = emulsifier_kg / (SURFACE_LOAD * 6.0 * oil_m3 / shear_d);
+        if coverage < MIN_COVERAGE {
+            // Bare droplets coalesce on contact
+            return EmulsionState::Broken {
+                reason: BreakReason::InsufficientEmulsifier,
+            };
+        }
+        let coverage_d = 6.0 * oil_m3 * SURFACE_LOAD / emulsifier_kg;
+        let diameter = shear_d.max(coverage_d);
+
+        // Creaming through a continuous phase thickened by the droplets
+        let relative = (1.0 - phi / MAX_OIL_FRACTION).max(1e-6);
+        let viscosity =
+            water_viscosity(self.temperature_c) * relative.powf(-2.5 * MAX_OIL_FRACTION);
+        let radius = diameter / 2.0;
+        let velocity = 2.0 * radius * radius * DENSITY_DIFFERENCE * 9.81 / (9.0 * viscosity);
+        let lifetime = CREAMING_HEIGHT / velocity;
+        let stability = lifetime / (lifetime + REFERENCE_LIFETIME_S);
+
+        if stability < MIN_STABILITY {
+            let reason = if coverage_d > shear_d {
+                BreakReason::InsufficientEmulsifier
+            } else {
+                BreakReason::Creamed
+            };
+            return EmulsionState::Broken { reason };
+        }
+        EmulsionState::Stable {
+            droplet_diameter_um: diameter * 1e6,
+            stability,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mayonnaise() -> EmulsionInput {
+        // One yolk (~17 g: 1.5 g lecithin + protein), 150 ml oil, lemon juice and yolk water
+        EmulsionInput {
+            oil_ml: 150.0,
+            water_ml: 35.0,
+            emulsifier_g: 17.0 * (0.09 + 0.1 * 0.16),
+            device: ShearDevice::Whisk,
+            duration_s: 180.0,
+            temperature_c: 20.0,
+            max_temperature_c: 65.0,
+        }
+    }
+
+    #[test]
+    fn test_mayonnaise_is_stable() {
+        let state = mayonnaise().evaluate();
+        assert_eq!(state.phase(), Phase::Emulsion);
+        assert!(state.stability() > 0.9);
+    }
+
+    #[test]
+    fn test_too_much_oil_breaks() {
+        let state = EmulsionInput {
+            oil_ml: 500.0,
+            ..mayonnaise()
+        }
+        .evaluate();
+        assert_eq!(
+            state,
+            EmulsionState::Broken {
+                reason: BreakReason::TooMuchOil
+            }
+        );
+        assert_eq!(state.phase(), Phase::Liquid);
+    }
+
+    #[test]
+    fn test_overheated_hollandaise_breaks() {
+        let state = EmulsionInput {
+            temperature_c: 75.0,
+            ..mayonnaise()
+        }
+        .evaluate();
+        assert_eq!(
+            state,
+            EmulsionState::Broken {
+                reason: BreakReason::TooHot
+            }
+        );
+    }
+
+    #[test]
+    fn test_vinaigrette_without_emulsifier_separates() {
+        let vinaigrette = EmulsionInput {
+            oil_ml: 90.0,
+            water_ml: 30.0,
+            emulsifier_g: 0.01,
+            ..mayonnaise()
+        };
+        assert_eq!(vinaigrette.evaluate().phase(), Phase::Liquid);
+    }
+
+    #[test]
+    fn test_blender_makes_smaller_droplets() {
+        let whisked = mayonnaise().evaluate();
+        let blended = EmulsionInput {
+            device: ShearDevice::Blender,
+            ..mayonnaise()
+        }
+        .evaluate();
+        match (whisked, blended) {
+            (
+                EmulsionState::Stable {
+                    droplet_diameter_um: w,
+                    ..
+                },
+                EmulsionState::Stable {
+                    droplet_diameter_um: b,
+                    ..
+                },
+            ) => assert!(b < w),
+            other => panic!("expected two stable emulsions, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_device_for_process() {
+        assert_eq!(
+            ShearDevice::for_process(&ProcessType::Blend),
+            Some(ShearDevice::Blender)
+        );
+        assert_eq!(ShearDevice::for_process(&ProcessType::Fry), None);
+    }
+}
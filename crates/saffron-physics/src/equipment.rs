@@ -0,0 +1,760 @@
+//! # Equipment Models
+//!
+//! Physical models for the `equipment` block: heat sources (burner power and
+//! efficiency), cookware (thermal mass and conductivity by material) and
+//! ovens (air convection plus wall radiation).
+//!
+//! A `Heat(pan, to:, using: stove)` step becomes a lumped heat-up problem:
+//!
+//! ```text
+//! C dT/dt = η P − h A (T − T_air) − ε σ A (T⁴ − T_air⁴)
+//! ```
+//!
+//! where `C` is the thermal mass of the cookware and its contents and `η P` is
+//! the burner power that actually reaches the pan. The losses leave through
+//! the pan wall, whose conduction resistance `d / (k A)` sits in series with
+//! the air side.
+
+use crate::conduction::BoundaryCondition;
+use crate::integrator::{ImplicitEuler, IntegrationError, Integrator, OdeSystem};
+use crate::units::Quantity;
+use saffron_ast::{EquipmentDecl, Expr, Param};
+use serde::{Deserialize, Serialize};
+
+/// Stefan–Boltzmann constant, W/(m^2*K^4)
+const STEFAN_BOLTZMANN: f64 = 5.670e-8;
+
+/// Natural convection from a hot pan to kitchen air, W/(m^2*K)
+const PAN_AIR_CONVECTION: f64 = 10.0;
+
+/// Room temperature assumed when none is given, °C
+pub const KITCHEN_AIR_C: f64 = 22.0;
+
+/// Longest heat-up [`HeatUp::time_to_temperature`] simulates, s
+const MAX_HEAT_UP_S: f64 = 4.0 * 3600.0;
+
+// ---------------------------------------------------------------------------
+// Heat sources
+// ---------------------------------------------------------------------------
+
+/// How a heat source produces heat
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HeatSourceKind {
+    Gas,
+    Electric,
+    Induction,
+    Charcoal,
+}
+
+impl HeatSourceKind {
+    /// Share of the rated power that ends up in the cookware
+    pub fn efficiency(self) -> f64 {
+        match self {
+            // Most of the flame's heat goes up past the pan
+            HeatSourceKind::Gas => 0.40,
+            HeatSourceKind::Electric => 0.70,
+            HeatSourceKind::Induction => 0.85,
+            HeatSourceKind::Charcoal => 0.25,
+        }
+    }
+
+    /// Rated power when the declaration gives none, W
+    pub fn default_power_w(self) -> f64 {
+        match self {
+            HeatSourceKind::Gas => 2500.0,
+            HeatSourceKind::Electric => 2000.0,
+            HeatSourceKind::Induction => 2000.0,
+            HeatSourceKind::Charcoal => 4000.0,
+        }
+    }
+
+    /// Match an enum variant name from a recipe (`Grill(type: .Charcoal)`)
+    pub fn from_variant(variant: &str) -> Option<Self> {
+        match variant {
+            "Gas" => Some(HeatSourceKind::Gas),
+            "Electric" => Some(HeatSourceKind::Electric),
+            "Induction" => Some(HeatSourceKind::Induction),
+            "Charcoal" | "Wood" => Some(HeatSourceKind::Charcoal),
+            _ => None,
+        }
+    }
+}
+
+/// A burner, hob or fire
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HeatSource {
+    pub kind: HeatSourceKind,
+    /// Rated power, W
+    pub power_w: f64,
+}
+
+impl HeatSource {
+    pub fn new(kind: HeatSourceKind, power_w: f64) -> Self {
+        Self { kind, power_w }
+    }
+
+    /// Power delivered to the cookware, W
+    pub fn delivered_power_w(&self) -> f64 {
+        self.power_w * self.kind.efficiency()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Cookware
+// ---------------------------------------------------------------------------
+
+/// Cookware material
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CookwareMaterial {
+    StainlessSteel,
+    CastIron,
+    Aluminum,
+    Copper,
+    CarbonSteel,
+}
+
+impl CookwareMaterial {
+    /// Thermal conductivity, W/(m*K)
+    pub fn conductivity(self) -> f64 {
+        match self {
+            CookwareMaterial::StainlessSteel => 16.0,
+            CookwareMaterial::CastIron => 52.0,
+            CookwareMaterial::Aluminum => 205.0,
+            CookwareMaterial::Copper => 385.0,
+            CookwareMaterial::CarbonSteel => 45.0,
+        }
+    }
+
+    /// Density, kg/m^3
+    pub fn density(self) -> f64 {
+        match self {
+            CookwareMaterial::StainlessSteel => 8000.0,
+            CookwareMaterial::CastIron => 7200.0,
+            CookwareMaterial::Aluminum => 2700.0,
+            CookwareMaterial::Copper => 8940.0,
+            CookwareMaterial::CarbonSteel => 7850.0,
+        }
+    }
+
+    /// Specific heat, J/(kg*K)
+    pub fn specific_heat(self) -> f64 {
+        match self {
+            CookwareMaterial::StainlessSteel => 500.0,
+            CookwareMaterial::CastIron => 460.0,
+            CookwareMaterial::Aluminum => 900.0,
+            CookwareMaterial::Copper => 385.0,
+            CookwareMaterial::CarbonSteel => 490.0,
+        }
+    }
+
+    /// Emissivity of the outside surface (seasoned iron is nearly black)
+    pub fn emissivity(self) -> f64 {
+        match self {
+            CookwareMaterial::StainlessSteel => 0.3,
+            CookwareMaterial::CastIron => 0.8,
+            CookwareMaterial::Aluminum => 0.1,
+            CookwareMaterial::Copper => 0.05,
+            CookwareMaterial::CarbonSteel => 0.7,
+        }
+    }
+
+    /// Typical wall thickness, m
+    pub fn typical_thickness_m(self) -> f64 {
+        match self {
+            CookwareMaterial::StainlessSteel => 0.0025,
+            CookwareMaterial::CastIron => 0.005,
+            CookwareMaterial::Aluminum => 0.004,
+            CookwareMaterial::Copper => 0.0025,
+            CookwareMaterial::CarbonSteel => 0.0025,
+        }
+    }
+
+    /// Match an enum variant name from a recipe (`.StainlessSteel`)
+    pub fn from_variant(variant: &str) -> Option<Self> {
+        match variant {
+            "StainlessSteel" => Some(CookwareMaterial::StainlessSteel),
+            "CastIron" => Some(CookwareMaterial::CastIron),
+            "Aluminum" | "Aluminium" => Some(CookwareMaterial::Aluminum),
+            "Copper" => Some(CookwareMaterial::Copper),
+            "CarbonSteel" => Some(CookwareMaterial::CarbonSteel),
+            _ => None,
+        }
+    }
+}
+
+/// A pan or pot, modelled as a flat disc base with a cylindrical wall
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Cookware {
+    pub material: CookwareMaterial,
+    pub diameter_m: f64,
+    pub wall_height_m: f64,
+    pub thickness_m: f64,
+}
+
+impl Cookware {
+    /// A frying pan: low walls, about a sixth of the diameter
+    pub fn frying_pan(material: CookwareMaterial, diameter_m: f64) -> Self {
+        Self {
+            material,
+            diameter_m,
+            wall_height_m: diameter_m / 6.0,
+            thickness_m: material.typical_thickness_m(),
+        }
+    }
+
+    /// A pot sized to hold `capacity_l`, with height three quarters of its diameter
+    pub fn pot(material: CookwareMaterial, capacity_l: f64) -> Self {
+        let volume = capacity_l / 1000.0;
+        let diameter_m = (4.0 * volume / (0.75 * std::f64::consts::PI)).cbrt();
+        Self {
+            material,
+            diameter_m,
+            wall_height_m: 0.75 * diameter_m,
+            thickness_m: material.typical_thickness_m(),
+        }
+    }
+
+    /// Area of the base in contact with the burner and the food, m^2
+    pub fn base_area_m2(&self) -> f64 {
+        std::f64::consts::PI * self.diameter_m * self.diameter_m / 4.0
+    }
+
+    /// Outside wall area, m^2
+    pub fn wall_area_m2(&self) -> f64 {
+        std::f64::consts::PI * self.diameter_m * self.wall_height_m
+    }
+
+    pub fn mass_kg(&self) -> f64 {
+        (self.base_area_m2() + self.wall_area_m2()) * self.thickness_m * self.material.density()
+    }
+
+    /// Heat needed to raise the empty cookware by one kelvin, J/K
+    pub fn thermal_mass(&self) -> f64 {
+        self.mass_kg() * self.material.specific_heat()
+    }
+
+    /// Conductance through the metal, from the heated side to the outer surface, W/K
+    pub fn wall_conductance(&self) -> f64 {
+        self.material.conductivity() * (self.base_area_m2() + self.wall_area_m2())
+            / self.thickness_m
+    }
+
+    /// Heat lost to the kitchen from the cooking surface and walls, W
+    pub fn heat_loss_w(&self, temperature_c: f64, air_c: f64) -> f64 {
+        let area = self.base_area_m2() + self.wall_area_m2();
+        let t = temperature_c + 273.15;
+        let ta = air_c + 273.15;
+        let surface = area
+            * (PAN_AIR_CONVECTION * (t - ta)
+                + self.material.emissivity() * STEFAN_BOLTZMANN * (t.powi(4) - ta.powi(4)));
+        surface / self.wall_resistance_factor(temperature_c)
+    }
+
+    /// d(heat_loss_w)/dT, W/K
+    fn heat_loss_slope(&self, temperature_c: f64) -> f64 {
+        self.surface_loss_slope(temperature_c) / self.wall_resistance_factor(temperature_c)
+    }
+
+    /// d(surface loss)/dT with no wall resistance, W/K
+    fn surface_loss_slope(&self, temperature_c: f64) -> f64 {
+        let area = self.base_area_m2() + self.wall_area_m2();
+        let t = temperature_c + 273.15;
+        area * (PAN_AIR_CONVECTION
+            + 4.0 * self.material.emissivity() * STEFAN_BOLTZMANN * t.powi(3))
+    }
+
+    /// `1 + R_wall / R_air`: how much the wall conduction resistance cuts the losses
+    fn wall_resistance_factor(&self, temperature_c: f64) -> f64 {
+        1.0 + self.surface_loss_slope(temperature_c) / self.wall_conductance()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Ovens
+// ---------------------------------------------------------------------------
+
+/// An oven cavity
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Oven {
+    pub temperature_c: f64,
+    /// Fan-assisted (convection) oven
+    pub fan: bool,
+}
+
+impl Oven {
+    pub fn new(temperature_c: f64, fan: bool) -> Self {
+        Self { temperature_c, fan }
+    }
+
+    /// Air convection coefficient, W/(m^2*K)
+    pub fn convection_h(&self) -> f64 {
+        if self.fan {
+            35.0
+        } else {
+            12.0
+        }
+    }
+
+    /// Linearized radiation from the oven walls to a food surface near `surface_c`, W/(m^2*K)
+    pub fn radiation_h(&self, surface_c: f64) -> f64 {
+        // Food surfaces and enamelled walls are both close to black bodies
+        let emissivity = 0.85;
+        let t_wall = self.temperature_c + 273.15;
+        let t_surf = surface_c + 273.15;
+        emissivity * STEFAN_BOLTZMANN * (t_wall * t_wall + t_surf * t_surf) * (t_wall + t_surf)
+    }
+
+    /// Boundary condition for food in the oven, with radiation linearized at `surface_c`
+    pub fn boundary(&self, surface_c: f64) -> BoundaryCondition {
+        BoundaryCondition::Convective {
+            ambient_c: self.temperature_c,
+            h: self.convection_h() + self.radiation_h(surface_c),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Declarations
+// ---------------------------------------------------------------------------
+
+/// Physical model for an entry in the `equipment` block
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Equipment {
+    Source(HeatSource),
+    Cookware(Cookware),
+    Oven(Oven),
+    /// A grill is its own heat source with a grate as the cooking surface
+    Grill {
+        source: HeatSource,
+        grate: Cookware,
+    },
+}
+
+impl Equipment {
+    /// Build from a declaration such as `GasStove(power: 2000.watts)` or
+    /// `FryingPan(diameter: 24.cm, material: .StainlessSteel)`.
+    ///
+    /// Returns `None` for equipment without a thermal role (spatulas, tongs).
+    pub fn from_decl(decl: &EquipmentDecl) -> Option<Self> {
+        let params = &decl.params;
+        let material = variant_param(params, "material")
+            .and_then(CookwareMaterial::from_variant)
+            .unwrap_or(CookwareMaterial::StainlessSteel);
+        let source = |kind: HeatSourceKind| {
+            HeatSource::new(
+                kind,
+                power_param(params).unwrap_or_else(|| kind.default_power_w()),
+            )
+        };
+
+        match decl.type_ref.name.as_str() {
+            "GasStove" => Some(Equipment::Source(source(HeatSourceKind::Gas))),
+            "ElectricStove" => Some(Equipment::Source(source(HeatSourceKind::Electric))),
+            "InductionStove" | "InductionCooktop" => {
+                Some(Equipment::Source(source(HeatSourceKind::Induction)))
+            }
+            "FryingPan" | "Skillet" | "Wok" => {
+                let diameter = length_param(params, "diameter").unwrap_or(0.24);
+                Some(Equipment::Cookware(Cookware::frying_pan(
+                    material, diameter,
+                )))
+            }
+            "Pot" | "Saucepan" | "StockPot" => {
+                let capacity = volume_param(params, "capacity").unwrap_or(4.0);
+                Some(Equipment::Cookware(Cookware::pot(material, capacity)))
+            }
+            "Oven" | "ConvectionOven" => {
+                let fan = decl.type_ref.name == "ConvectionOven"
+                    || matches!(
+                        params
+                            .iter()
+                            .find(|p| p.name == "fan" || p.name == "convection"),
+                        Some(Param {
+                            value: Expr::BoolLiteral { value: true, .. },
+                            ..
+                        })
+                    );
+                let temperature = temperature_param(params, "temperature").unwrap_or(180.0);
+                Some(Equipment::Oven(Oven::new(temperature, fan)))
+            }
+            "Grill" => {
+                let kind = variant_param(params, "type")
+                    .and_then(HeatSourceKind::from_variant)
+                    .unwrap_or(HeatSourceKind::Charcoal);
+                let grate_material = variant_param(params, "material")
+                    .and_then(CookwareMaterial::from_variant)
+                    .unwrap_or(CookwareMaterial::CastIron);
+                Some(Equipment::Grill {
+                    source: source(kind),
+                    grate: Cookware::frying_pan(grate_material, 0.45),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+fn variant_param<'a>(params: &'a [Param], name: &str) -> Option<&'a str> {
+    params
+        .iter()
+        .find(|p| p.name == name)
+        .and_then(|p| match &p.value {
+            Expr::EnumVariant { variant, .. } => Some(variant.as_str()),
+            _ => None,
+        })
+}
+
+fn power_param(params: &[Param]) -> Option<f64> {
+    match Quantity::from_param(params, "power")? {
+        Quantity::Power(power) => Some(power.si()),
+        _ => None,
+    }
+}
+
+fn length_param(params: &[Param], name: &str) -> Option<f64> {
+    match Quantity::from_param(params, name)? {
+        Quantity::Length(length) => Some(length.si()),
+        _ => None,
+    }
+}
+
+fn volume_param(params: &[Param], name: &str) -> Option<f64> {
+    match Quantity::from_param(params, name)? {
+        Quantity::Volume(volume) => Some(volume.liters()),
+        _ => None,
+    }
+}
+
+fn temperature_param(params: &[Param], name: &str) -> Option<f64> {
+    match Quantity::from_param(params, name)? {
+        Quantity::Temperature(temperature) => Some(temperature.celsius()),
+        _ => None,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Heat-up
+// ---------------------------------------------------------------------------
+
+/// Cookware (and anything in it) heated by a source
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HeatUp {
+    pub source: HeatSource,
+    pub cookware: Cookware,
+    /// Thermal mass of the contents, J/K (e.g. `4186 × kg` of water)
+    pub contents_thermal_mass: f64,
+    pub air_c: f64,
+}
+
+impl HeatUp {
+    /// An empty pan in a kitchen at [`KITCHEN_AIR_C`]
+    pub fn new(source: HeatSource, cookware: Cookware) -> Self {
+        Self {
+            source,
+            cookware,
+            contents_thermal_mass: 0.0,
+            air_c: KITCHEN_AIR_C,
+        }
+    }
+
+    /// Add contents of `mass_kg` with `specific_heat` J/(kg*K)
+    pub fn with_contents(mut self, mass_kg: f64, specific_heat: f64) -> Self {
+        self.contents_thermal_mass += mass_kg * specific_heat;
+        self
+    }
+
+    fn thermal_mass(&self) -> f64 {
+        self.cookware.thermal_mass() + self.contents_thermal_mass
+    }
+
+    /// Advance the temperature by `dt` seconds with implicit Euler
+    pub fn step(&self, temperature_c: f64, dt: f64) -> Result<f64, IntegrationError> {
+        let mut y = [temperature_c];
+        ImplicitEuler::new(dt).integrate(self, 0.0, dt, &mut y)?;
+        Ok(y[0])
+    }
+
+    /// Temperature where burner input balances losses, °C
+    pub fn steady_state_c(&self) -> f64 {
+        let power = self.source.delivered_power_w();
+        let (mut lo, mut hi) = (self.air_c, self.air_c + 2000.0);
+        for _ in 0..100 {
+            let mid = (lo + hi) / 2.0;
+            if self.cookware.heat_loss_w(mid, self.air_c) < power {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
+
+    /// `(time_s, temperature_c)` samples from `start_c` until `target_c` or `max_s`
+    pub fn curve(
+        &self,
+        start_c: f64,
+        target_c: f64,
+        dt: f64,
+        max_s: f64,
+    ) -> Result<Vec<(f64, f64)>, IntegrationError> {
+        let mut samples = vec![(0.0, start_c)];
+        let (mut time, mut temp) = (0.0, start_c);
+        while temp < target_c && time < max_s {
+            temp = self.step(temp, dt)?;
+            time += dt;
+            samples.push((time, temp));
+        }
+        Ok(samples)
+    }
+
+    /// Seconds to reach `target_c` from `start_c`, or `None` if the source
+    /// cannot get there within [`MAX_HEAT_UP_S`] or the integration fails
+    pub fn time_to_temperature(&self, start_c: f64, target_c: f64) -> Option<f64> {
+        if target_c <= start_c {
+            return Some(0.0);
+        }
+        if target_c >= self.steady_state_c() {
+            return None;
+        }
+        let dt = 1.0;
+        let (mut time, mut temp) = (0.0, start_c);
+        while time < MAX_HEAT_UP_S {
+            let next = self.step(temp, dt).ok()?;
+            if next >= target_c {
+                // Interpolate within the last step
+                return Some(time + dt * (target_c - temp) / (next - temp));
+            }
+            if next <= temp {
+                // Stalled short of the target
+                return None;
+            }
+            temp = next;
+            time += dt;
+        }
+        None
+    }
+}
+
+impl OdeSystem for HeatUp {
+    fn dimension(&self) -> usize {
+        1
+    }
+
+    fn derivative(&self, _t: f64, y: &[f64], dydt: &mut [f64]) {
+        let loss = self.cookware.heat_loss_w(y[0], self.air_c);
+        dydt[0] = (self.source.delivered_power_w() - loss) / self.thermal_mass();
+    }
+
+    fn jacobian(&self, _t: f64, y: &[f64], jacobian: &mut [f64]) {
+        jacobian[0] = -self.cookware.heat_loss_slope(y[0]) / self.thermal_mass();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use saffron_ast::{Span, TypeRef, Unit};
+
+    fn span() -> Span {
+        Span {
+            file: "test.saffron".into(),
+            start_line: 1,
+            start_col: 1,
+            end_line: 1,
+            end_col: 1,
+            byte_offset: 0,
+            byte_length: 0,
+        }
+    }
+
+    fn decl(type_name: &str, params: Vec<(&str, Expr)>) -> EquipmentDecl {
+        EquipmentDecl {
+            name: "x".into(),
+            type_ref: TypeRef {
+                name: type_name.into(),
+                generics: vec![],
+                span: span(),
+            },
+            params: params
+                .into_iter()
+                .map(|(name, value)| Param {
+                    name: name.into(),
+                    value,
+                    span: span(),
+                })
+                .collect(),
+            span: span(),
+        }
+    }
+
+    fn unit(value: f64, unit: Unit) -> Expr {
+        Expr::UnitLiteral {
+            value,
+            unit,
+            span: span(),
+        }
+    }
+
+    fn variant(name: &str) -> Expr {
+        Expr::EnumVariant {
+            variant: name.into(),
+            span: span(),
+        }
+    }
+
+    #[test]
+    fn test_fried_egg_pan_heats_in_minutes() {
+        let stove = HeatSource::new(HeatSourceKind::Gas, 2000.0);
+        let pan = Cookware::frying_pan(CookwareMaterial::StainlessSteel, 0.24);
+        let time = HeatUp::new(stove, pan)
+            .time_to_temperature(KITCHEN_AIR_C, 180.0)
+            .unwrap();
+        assert!(time > 60.0 && time < 360.0, "took {time}s");
+    }
+
+    #[test]
+    fn test_cast_iron_is_slower_than_carbon_steel() {
+        let stove = HeatSource::new(HeatSourceKind::Gas, 2000.0);
+        let iron = HeatUp::new(
+            stove,
+            Cookware::frying_pan(CookwareMaterial::CastIron, 0.26),
+        );
+        let steel = HeatUp::new(
+            stove,
+            Cookware::frying_pan(CookwareMaterial::CarbonSteel, 0.26),
+        );
+        assert!(
+            iron.time_to_temperature(20.0, 200.0).unwrap()
+                > steel.time_to_temperature(20.0, 200.0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_induction_beats_gas_at_same_rating() {
+        let pan = Cookware::frying_pan(CookwareMaterial::StainlessSteel, 0.24);
+        let gas = HeatUp::new(HeatSource::new(HeatSourceKind::Gas, 2000.0), pan);
+        let induction = HeatUp::new(HeatSource::new(HeatSourceKind::Induction, 2000.0), pan);
+        assert!(
+            induction.time_to_temperature(20.0, 180.0).unwrap()
+                < gas.time_to_temperature(20.0, 180.0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_pot_of_water_boils_in_reasonable_time() {
+        let heat = HeatUp::new(
+            HeatSource::new(HeatSourceKind::Gas, 3000.0),
+            Cookware::pot(CookwareMaterial::StainlessSteel, 4.0),
+        )
+        .with_contents(3.0, 4186.0);
+        let time = heat.time_to_temperature(15.0, 100.0).unwrap();
+        assert!(time > 8.0 * 60.0 && time < 25.0 * 60.0, "took {time}s");
+    }
+
+    #[test]
+    fn test_weak_burner_cannot_reach_target() {
+        let heat = HeatUp::new(
+            HeatSource::new(HeatSourceKind::Electric, 100.0),
+            Cookware::frying_pan(CookwareMaterial::CastIron, 0.3),
+        );
+        assert!(heat.steady_state_c() < 250.0);
+        assert_eq!(heat.time_to_temperature(20.0, 250.0), None);
+    }
+
+    #[test]
+    fn test_heat_up_gives_up_instead_of_hanging() {
+        // Contents so massive the target is out of reach within the cap
+        let heat = HeatUp::new(
+            HeatSource::new(HeatSourceKind::Gas, 3000.0),
+            Cookware::pot(CookwareMaterial::StainlessSteel, 4.0),
+        )
+        .with_contents(1.0e4, 4186.0);
+        assert!(heat.steady_state_c() > 100.0);
+        assert_eq!(heat.time_to_temperature(15.0, 100.0), None);
+    }
+
+    #[test]
+    fn test_wall_conduction_limits_losses() {
+        let pan = Cookware::frying_pan(CookwareMaterial::StainlessSteel, 0.24);
+        let thick = Cookware {
+            thickness_m: 10.0 * pan.thickness_m,
+            ..pan
+        };
+        assert!(thick.wall_conductance() < pan.wall_conductance());
+        assert!(thick.heat_loss_w(200.0, 20.0) < pan.heat_loss_w(200.0, 20.0));
+    }
+
+    #[test]
+    fn test_curve_is_monotonic_and_bounded() {
+        let heat = HeatUp::new(
+            HeatSource::new(HeatSourceKind::Gas, 2000.0),
+            Cookware::frying_pan(CookwareMaterial::Aluminum, 0.24),
+        );
+        let curve = heat.curve(20.0, 1000.0, 30.0, 3600.0).unwrap();
+        let steady = heat.steady_state_c();
+        for pair in curve.windows(2) {
+            assert!(pair[1].1 >= pair[0].1);
+            assert!(pair[1].1 <= steady + 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_fan_oven_transfers_more_heat() {
+        let still = Oven::new(200.0, false).boundary(20.0);
+        let fan = Oven::new(200.0, true).boundary(20.0);
+        match (still, fan) {
+            (
+                BoundaryCondition::Convective { h: h_still, .. },
+                BoundaryCondition::Convective { h: h_fan, .. },
+            ) => assert!(h_fan > h_still),
+            other => panic!("expected convective boundaries, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_from_decl() {
+        let stove = Equipment::from_decl(&decl(
+            "GasStove",
+            vec![("power", unit(2000.0, Unit::Watts))],
+        ));
+        assert_eq!(
+            stove,
+            Some(Equipment::Source(HeatSource::new(
+                HeatSourceKind::Gas,
+                2000.0
+            )))
+        );
+
+        let pan = Equipment::from_decl(&decl(
+            "FryingPan",
+            vec![
+                ("diameter", unit(24.0, Unit::Centimeters)),
+                ("material", variant("StainlessSteel")),
+            ],
+        ));
+        match pan {
+            Some(Equipment::Cookware(pan)) => {
+                assert_eq!(pan.material, CookwareMaterial::StainlessSteel);
+                assert!((pan.diameter_m - 0.24).abs() < 1e-12);
+            }
+            other => panic!("expected cookware, got {other:?}"),
+        }
+
+        let grill = Equipment::from_decl(&decl("Grill", vec![("type", variant("Charcoal"))]));
+        assert!(matches!(
+            grill,
+            Some(Equipment::Grill {
+                source: HeatSource {
+                    kind: HeatSourceKind::Charcoal,
+                    ..
+                },
+                ..
+            })
+        ));
+
+        assert_eq!(
+            Equipment::from_decl(&decl("Spatula", vec![("material", variant("Silicone"))])),
+            None
+        );
+    }
+}
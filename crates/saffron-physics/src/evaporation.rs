@@ -0,0 +1,356 @@
+//! # Evaporation and Boiling
+//!
+//! Vapor pressure and boiling point of water from the Antoine equation
+//!
+//! ```text
+//! log10(P_mmHg) = A - B / (C + T)
+//! ```
+//!
+//! plus a mass-loss model for open, ajar and lidded pots. Below the boiling
+//! point water leaves the surface by Dalton-type evaporation; at the boiling
+//! point any surplus heat boils water off at the latent heat of vaporization.
+//! Solutes stay behind, so `Reduce`, `Simmer` and `Boil` concentrate a liquid.
+
+use crate::integrator::{check_step_size, IntegrationError};
+use saffron_ast::{Expr, Param};
+use serde::{Deserialize, Serialize};
+
+/// Standard atmosphere at sea level, kPa
+pub const SEA_LEVEL_PRESSURE_KPA: f64 = 101.325;
+
+const MMHG_TO_KPA: f64 = 0.133_322;
+
+/// Antoine constants for water, `(A, B, C)` with P in mmHg and T in °C
+const ANTOINE_LOW: (f64, f64, f64) = (8.07131, 1730.63, 233.426); // 1–100°C
+const ANTOINE_HIGH: (f64, f64, f64) = (8.14019, 1810.94, 244.485); // 99–374°C
+
+/// Dalton evaporation coefficient for a still water surface, kg/(m^2*s*Pa)
+const EVAPORATION_COEFFICIENT: f64 = 2.6e-8;
+
+/// Saturated vapor pressure of water at `temperature_c`, in kPa
+pub fn vapor_pressure_kpa(temperature_c: f64) -> f64 {
+    let (a, b, c) = if temperature_c <= 100.0 {
+        ANTOINE_LOW
+    } else {
+        ANTOINE_HIGH
+    };
+    10f64.powf(a - b / (c + temperature_c)) * MMHG_TO_KPA
+}
+
+/// Boiling point of water at an ambient pressure, in °C
+pub fn boiling_point_c(pressure_kpa: f64) -> f64 {
+    let p_mmhg = pressure_kpa / MMHG_TO_KPA;
+    let invert = |(a, b, c): (f64, f64, f64)| b / (a - p_mmhg.log10()) - c;
+    let t = invert(ANTOINE_LOW);
+    if t <= 100.0 {
+        t
+    } else {
+        invert(ANTOINE_HIGH)
+    }
+}
+
+/// Barometric pressure at `altitude_m` above sea level (International Standard Atmosphere), kPa
+pub fn pressure_at_altitude_kpa(altitude_m: f64) -> f64 {
+    SEA_LEVEL_PRESSURE_KPA * (1.0 - 2.25577e-5 * altitude_m).powf(5.25588)
+}
+
+/// Latent heat of vaporization of water at `temperature_c` (Watson correlation), J/kg
+pub fn latent_heat_vaporization(temperature_c: f64) -> f64 {
+    const CRITICAL_K: f64 = 647.1;
+    const L_AT_100C: f64 = 2.257e6;
+    let t = (temperature_c + 273.15).min(CRITICAL_K);
+    L_AT_100C * ((CRITICAL_K - t) / (CRITICAL_K - 373.15)).powf(0.38)
+}
+
+/// Pot lid position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Lid {
+    Open,
+    Ajar,
+    Closed,
+}
+
+impl Lid {
+    /// Share of generated vapor that escapes instead of condensing back into the pot
+    pub fn escape_fraction(self) -> f64 {
+        match self {
+            Lid::Open => 1.0,
+            Lid::Ajar => 0.5,
+            Lid::Closed => 0.1,
+        }
+    }
+
+    /// Read a `lid:` param (`lid: false` → open), defaulting to `Open`
+    pub fn from_params(params: &[Param]) -> Self {
+        params
+            .iter()
+            .find(|p| p.name == "lid")
+            .map(|p| match &p.value {
+                Expr::BoolLiteral { value: true, .. } => Lid::Closed,
+                Expr::EnumVariant { variant, .. } if variant == "Ajar" => Lid::Ajar,
+                Expr::EnumVariant { variant, .. } if variant == "Closed" => Lid::Closed,
+                _ => Lid::Open,
+            })
+            .unwrap_or(Lid::Open)
+    }
+}
+
+/// A liquid being heated: water plus dissolved or suspended solids
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LiquidState {
+    pub water_kg: f64,
+    pub solids_kg: f64,
+    pub temperature_c: f64,
+}
+
+impl LiquidState {
+    /// Total mass, kg
+    pub fn mass_kg(&self) -> f64 {
+        self.water_kg + self.solids_kg
+    }
+
+    /// Solids concentration as a mass fraction (0.0–1.0)
+    pub fn concentration(&self) -> f64 {
+        let mass = self.mass_kg();
+        if mass > 0.0 {
+            self.solids_kg / mass
+        } else {
+            0.0
+        }
+    }
+
+    /// Approximate volume in liters, taking solids at the density of sugar (1.59 g/ml)
+    pub fn volume_l(&self) -> f64 {
+        self.water_kg + self.solids_kg / 1.59
+    }
+
+    /// Mass-weighted specific heat, J/(kg*K)
+    fn specific_heat(&self) -> f64 {
+        let mass = self.mass_kg();
+        if mass > 0.0 {
+            (self.water_kg * 4186.0 + self.solids_kg * 1500.0) / mass
+        } else {
+            4186.0
+        }
+    }
+}
+
+/// Result of one evaporation step
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EvaporationStep {
+    /// Water mass that left the pot, kg
+    pub evaporated_kg: f64,
+    /// Heat spent on vaporization (including vapor that condensed back), J
+    pub latent_heat_j: f64,
+}
+
+/// Evaporation from the free surface of a pot
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EvaporationModel {
+    pub surface_area_m2: f64,
+    pub lid: Lid,
+    pub ambient_pressure_kpa: f64,
+    pub air_temp_c: f64,
+    pub relative_humidity: f64, // 0.0–1.0
+}
+
+impl EvaporationModel {
+    /// Pot of `diameter_m` at sea level in a 22°C, 50% RH kitchen
+    pub fn new(diameter_m: f64, lid: Lid) -> Self {
+        let radius = diameter_m / 2.0;
+        Self {
+            surface_area_m2: std::f64::consts::PI * radius * radius,
+            lid,
+            ambient_pressure_kpa: SEA_LEVEL_PRESSURE_KPA,
+            air_temp_c: 22.0,
+            relative_humidity: 0.5,
+        }
+    }
+
+    /// Same pot at a given altitude
+    pub fn at_altitude(mut self, altitude_m: f64) -> Self {
+        self.ambient_pressure_kpa = pressure_at_altitude_kpa(altitude_m);
+        self
+    }
+
+    /// Boiling point at this model's ambient pressure, °C
+    pub fn boiling_point_c(&self) -> f64 {
+        boiling_point_c(self.ambient_pressure_kpa)
+    }
+
+    /// Advance the liquid by `dt` seconds with `heat_input_w` of net heat entering it
+    pub fn step(&self, state: &mut LiquidState, heat_input_w: f64, dt: f64) -> EvaporationStep {
+        if state.water_kg <= 0.0 {
+            return EvaporationStep {
+                evaporated_kg: 0.0,
+                latent_heat_j: 0.0,
+            };
+        }
+        let boiling = self.boiling_point_c();
+        let escape = self.lid.escape_fraction();
+
+        // Surface evaporation, driven by the vapor pressure deficit
+        let air_vapor = self.relative_humidity * vapor_pressure_kpa(self.air_temp_c);
+        let deficit_pa =
+            (vapor_pressure_kpa(state.temperature_c.min(boiling)) - air_vapor).max(0.0) * 1000.0;
+        let surface_kg =
+            (EVAPORATION_COEFFICIENT * self.surface_area_m2 * deficit_pa * escape * dt)
+                .min(state.water_kg);
+        let mut latent_j = surface_kg * latent_heat_vaporization(state.temperature_c);
+
+        // Sensible heating; anything above the boiling point becomes vapor
+        let heat_capacity = state.mass_kg() * state.specific_heat();
+        let mut temperature = state.temperature_c + (heat_input_w * dt - latent_j) / heat_capacity;
+        let mut boiled_kg = 0.0;
+        if temperature > boiling {
+            let surplus_j = (temperature - boiling) * heat_capacity;
+            let generated_kg = surplus_j / latent_heat_vaporization(boiling);
+            boiled_kg = (generated_kg * escape).min(state.water_kg - surface_kg);
+            latent_j += generated_kg * latent_heat_vaporization(boiling);
+            temperature = boiling;
+        }
+
+        let evaporated_kg = surface_kg + boiled_kg;
+        state.water_kg -= evaporated_kg;
+        state.temperature_c = temperature;
+        EvaporationStep {
+            evaporated_kg,
+            latent_heat_j: latent_j,
+        }
+    }
+
+    /// Simmer or reduce until volume drops to `target_fraction` of its starting value.
+    ///
+    /// Returns the elapsed time, or `None` if it takes longer than `max_duration_s`.
+    pub fn time_to_reduce(
+        &self,
+        state: &mut LiquidState,
+        target_fraction: f64,
+        heat_input_w: f64,
+        dt: f64,
+        max_duration_s: f64,
+    ) -> Result<Option<f64>, IntegrationError> {
+        check_step_size(dt)?;
+        let target_volume = state.volume_l() * target_fraction;
+        let mut elapsed = 0.0;
+        while elapsed < max_duration_s {
+            if state.volume_l() <= target_volume {
+                return Ok(Some(elapsed));
+            }
+            self.step(state, heat_input_w, dt);
+            elapsed += dt;
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vapor_pressure_at_boiling() {
+        assert!((vapor_pressure_kpa(100.0) - SEA_LEVEL_PRESSURE_KPA).abs() < 0.5);
+        assert!((vapor_pressure_kpa(20.0) - 2.34).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_boiling_point_at_altitude() {
+        assert!((boiling_point_c(SEA_LEVEL_PRESSURE_KPA) - 100.0).abs() < 0.1);
+        // Mexico City (~2240 m) boils around 92.5C
+        let bp = boiling_point_c(pressure_at_altitude_kpa(2240.0));
+        assert!(bp > 92.0 && bp < 93.5, "got {bp}");
+        // Pressure cooker at ~2 atm
+        assert!(boiling_point_c(2.0 * SEA_LEVEL_PRESSURE_KPA) > 119.0);
+    }
+
+    #[test]
+    fn test_latent_heat() {
+        assert!((latent_heat_vaporization(100.0) - 2.257e6).abs() < 1.0);
+        assert!(latent_heat_vaporization(25.0) > latent_heat_vaporization(100.0));
+    }
+
+    #[test]
+    fn test_rolling_boil_rate_matches_energy() {
+        // 3 kW into water already at 100C: ~1.33 g/s boils off
+        let model = EvaporationModel::new(0.24, Lid::Open);
+        let mut water = LiquidState {
+            water_kg: 2.0,
+            solids_kg: 0.0,
+            temperature_c: model.boiling_point_c(),
+        };
+        let mut lost = 0.0;
+        for _ in 0..60 {
+            lost += model.step(&mut water, 3000.0, 1.0).evaporated_kg;
+        }
+        let expected = 3000.0 * 60.0 / 2.257e6;
+        assert!((lost - expected).abs() / expected < 0.05);
+    }
+
+    #[test]
+    fn test_lid_reduces_loss() {
+        let open = EvaporationModel::new(0.24, Lid::Open);
+        let closed = EvaporationModel::new(0.24, Lid::Closed);
+        let start = LiquidState {
+            water_kg: 2.0,
+            solids_kg: 0.0,
+            temperature_c: 20.0,
+        };
+        let (mut a, mut b) = (start, start);
+        for _ in 0..900 {
+            open.step(&mut a, 2500.0, 1.0);
+            closed.step(&mut b, 2500.0, 1.0);
+        }
+        assert!(a.water_kg < b.water_kg);
+        assert!(b.temperature_c <= closed.boiling_point_c());
+    }
+
+    #[test]
+    fn test_reduce_concentrates() {
+        let model = EvaporationModel::new(0.2, Lid::Open);
+        let mut stock = LiquidState {
+            water_kg: 1.0,
+            solids_kg: 0.05,
+            temperature_c: 95.0,
+        };
+        let before = stock.concentration();
+        let t = model
+            .time_to_reduce(&mut stock, 0.5, 1500.0, 1.0, 7200.0)
+            .unwrap()
+            .unwrap();
+        assert!(t > 0.0);
+        assert!(stock.concentration() > 1.8 * before);
+        assert!((stock.solids_kg - 0.05).abs() < 1e-12);
+
+        for dt in [0.0, -1.0, f64::NAN] {
+            assert!(matches!(
+                model.time_to_reduce(&mut stock, 0.5, 1500.0, dt, 7200.0),
+                Err(IntegrationError::InvalidStepSize { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_lid_from_params() {
+        let span = saffron_ast::Span {
+            file: "boiled_pasta.saffron".into(),
+            start_line: 1,
+            start_col: 1,
+            end_line: 1,
+            end_col: 1,
+            byte_offset: 0,
+            byte_length: 0,
+        };
+        let lid = |value| Param {
+            name: "lid".into(),
+            value: Expr::BoolLiteral {
+                value,
+                span: span.clone(),
+            },
+            span: span.clone(),
+        };
+        assert_eq!(Lid::from_params(&[lid(false)]), Lid::Open);
+        assert_eq!(Lid::from_params(&[lid(true)]), Lid::Closed);
+        assert_eq!(Lid::from_params(&[]), Lid::Open);
+    }
+}
@@ -0,0 +1,393 @@
+//! # Fermentation and Leavening
+//!
+//! Temperature-dependent microbial growth for `Leaven`, `Ferment` and
+//! `Pickle`:
+//!
+//! - **Cardinal temperature model** (Rosso CTMI) — growth rate is zero at
+//!   `T_min` and `T_max` and peaks at `T_opt`.
+//! - **Yeast** in dough produces CO₂; part of it is held by the gluten network
+//!   and raises the dough volume until the network is fully stretched.
+//! - **Lactic acid bacteria** in brine grow logistically, slowed by salt and
+//!   their own acid, and produce lactic acid that lowers the pH from the
+//!   ingredient's starting value (SID `Composition.ph`).
+//!
+//! Both are ODE systems stepped with [`Rk45`].
+
+use crate::integrator::{IntegrationError, Integrator, OdeSystem, Rk45};
+use saffron_ast::ProcessType;
+use saffron_sid::Composition;
+use serde::{Deserialize, Serialize};
+
+/// Minimum, optimum and maximum growth temperatures
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CardinalTemperatures {
+    pub min_c: f64,
+    pub opt_c: f64,
+    pub max_c: f64,
+}
+
+impl CardinalTemperatures {
+    /// Relative growth rate (0.0–1.0) at `temperature_c`
+    pub fn factor(&self, temperature_c: f64) -> f64 {
+        let (t, min, opt, max) = (temperature_c, self.min_c, self.opt_c, self.max_c);
+        if t <= min || t >= max {
+            return 0.0;
+        }
+        let numerator = (t - max) * (t - min).powi(2);
+        let denominator =
+            (opt - min) * ((opt - min) * (t - opt) - (opt - max) * (opt + min - 2.0 * t));
+        (numerator / denominator).clamp(0.0, 1.0)
+    }
+}
+
+/// Microbial cultures with growth parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Culture {
+    /// Saccharomyces cerevisiae
+    BakersYeast,
+    /// Lactobacillus plantarum and relatives in vegetable ferments
+    LacticAcidBacteria,
+}
+
+impl Culture {
+    pub fn cardinal_temperatures(self) -> CardinalTemperatures {
+        match self {
+            Culture::BakersYeast => CardinalTemperatures {
+                min_c: 2.0,
+                opt_c: 35.0,
+                max_c: 45.0,
+            },
+            Culture::LacticAcidBacteria => CardinalTemperatures {
+                min_c: 5.0,
+                opt_c: 32.0,
+                max_c: 43.0,
+            },
+        }
+    }
+
+    /// Specific growth rate at the optimum temperature, 1/s
+    pub fn max_growth_rate(self) -> f64 {
+        match self {
+            Culture::BakersYeast => 0.2 / 3600.0,
+            Culture::LacticAcidBacteria => 0.6 / 3600.0,
+        }
+    }
+
+    /// Culture responsible for a process
+    pub fn for_process(process: &ProcessType) -> Option<Self> {
+        match process {
+            ProcessType::Leaven => Some(Culture::BakersYeast),
+            ProcessType::Ferment | ProcessType::Pickle => Some(Culture::LacticAcidBacteria),
+            _ => None,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Dough leavening
+// ---------------------------------------------------------------------------
+
+/// CO₂ released per gram of fresh yeast per second at the optimum, ml
+const YEAST_CO2_RATE: f64 = 3.5 / 60.0;
+
+/// Share of the CO₂ the gluten network holds
+const GAS_RETENTION: f64 = 0.5;
+
+/// Extra volume, as a multiple of the initial volume, at which the dough is fully stretched
+const MAX_EXTRA_RISE: f64 = 2.5;
+
+/// Dough density, g/ml
+const DOUGH_DENSITY: f64 = 1.1;
+
+/// A yeasted dough rising
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DoughFermentation {
+    /// Fresh yeast equivalent, g (instant dry yeast counts about three times)
+    pub yeast_g: f64,
+    pub initial_volume_ml: f64,
+    /// Yeast activity relative to the start (grows slowly during long rises)
+    activity: f64,
+    /// CO₂ released so far, ml
+    co2_ml: f64,
+}
+
+struct DoughSystem<'a> {
+    dough: &'a DoughFermentation,
+    temperature_factor: f64,
+}
+
+impl OdeSystem for DoughSystem<'_> {
+    fn dimension(&self) -> usize {
+        2
+    }
+
+    fn derivative(&self, _t: f64, y: &[f64], dydt: &mut [f64]) {
+        let culture = Culture::BakersYeast;
+        dydt[0] = culture.max_growth_rate() * self.temperature_factor * y[0];
+        dydt[1] = YEAST_CO2_RATE * self.dough.yeast_g * self.temperature_factor * y[0];
+    }
+}
+
+impl DoughFermentation {
+    pub fn new(dough_mass_g: f64, yeast_g: f64) -> Self {
+        Self {
+            yeast_g,
+            initial_volume_ml: dough_mass_g / DOUGH_DENSITY,
+            activity: 1.0,
+            co2_ml: 0.0,
+        }
+    }
+
+    /// Rise at `temperature_c` for `dt` seconds; the state is unchanged if integration fails
+    pub fn advance(&mut self, temperature_c: f64, dt: f64) -> Result<(), IntegrationError> {
+        let system = DoughSystem {
+            dough: self,
+            temperature_factor: Culture::BakersYeast
+                .cardinal_temperatures()
+                .factor(temperature_c),
+        };
+        let mut y = [self.activity, self.co2_ml];
+        Rk45::default().integrate(&system, 0.0, dt, &mut y)?;
+        self.activity = y[0];
+        self.co2_ml = y[1];
+        Ok(())
+    }
+
+    pub fn co2_ml(&self) -> f64 {
+        self.co2_ml
+    }
+
+    /// Current volume relative to the start, for `WaitUntil(dough.state.volume >= ..)`
+    pub fn rise_ratio(&self) -> f64 {
+        let held = GAS_RETENTION * self.co2_ml / self.initial_volume_ml;
+        1.0 + MAX_EXTRA_RISE * (1.0 - (-held / MAX_EXTRA_RISE).exp())
+    }
+
+    pub fn volume_ml(&self) -> f64 {
+        self.initial_volume_ml * self.rise_ratio()
+    }
+
+    /// Seconds at a constant `temperature_c` until the dough reaches `ratio`;
+    /// `None` if it takes longer than `max_s` or integration fails
+    pub fn time_to_rise(&self, ratio: f64, temperature_c: f64, max_s: f64) -> Option<f64> {
+        let mut dough = *self;
+        let step = 60.0;
+        let mut time = 0.0;
+        while time < max_s {
+            if dough.rise_ratio() >= ratio {
+                return Some(time);
+            }
+            dough.advance(temperature_c, step).ok()?;
+            time += step;
+        }
+        None
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Lactic fermentation
+// ---------------------------------------------------------------------------
+
+/// Population a vegetable ferment levels off at, CFU/g
+const MAX_POPULATION: f64 = 1e9;
+
+/// Lactic acid produced per CFU/g of growth, g/l
+const ACID_YIELD: f64 = 1.5e-8;
+
+/// pH below which lactic bacteria stop growing
+const MIN_GROWTH_PH: f64 = 3.2;
+
+/// pH above which acid does not inhibit growth
+const UNINHIBITED_PH: f64 = 4.8;
+
+/// Brine salt mass fraction that stops growth
+const MAX_SALT: f64 = 0.10;
+
+/// pH drop per decade of `1 + acid / buffer`
+const PH_SLOPE: f64 = 2.0;
+
+/// Buffering of vegetable juices, g/l lactic acid
+const BUFFER_G_PER_L: f64 = 0.5;
+
+/// A vegetable ferment or pickle brine
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LacticFermentation {
+    pub initial_ph: f64,
+    /// Brine salt mass fraction
+    pub salt_fraction: f64,
+    /// ln of the population in CFU/g
+    log_population: f64,
+    /// Lactic acid, g/l
+    acid_g_per_l: f64,
+}
+
+struct LacticSystem<'a> {
+    ferment: &'a LacticFermentation,
+    temperature_factor: f64,
+}
+
+impl OdeSystem for LacticSystem<'_> {
+    fn dimension(&self) -> usize {
+        2
+    }
+
+    fn derivative(&self, _t: f64, y: &[f64], dydt: &mut [f64]) {
+        let f = self.ferment;
+        let population = y[0].exp();
+        let ph = f.ph_for(y[1]);
+        let acid_factor = ((ph - MIN_GROWTH_PH) / (UNINHIBITED_PH - MIN_GROWTH_PH)).clamp(0.0, 1.0);
+        let salt_factor = (1.0 - f.salt_fraction / MAX_SALT).clamp(0.0, 1.0);
+        let mu = Culture::LacticAcidBacteria.max_growth_rate()
+            * self.temperature_factor
+            * acid_factor
+            * salt_factor
+            * (1.0 - population / MAX_POPULATION).max(0.0);
+        dydt[0] = mu;
+        dydt[1] = ACID_YIELD * mu * population;
+    }
+}
+
+impl LacticFermentation {
+    /// Fresh vegetables carry around 10³ CFU/g of lactic bacteria
+    pub fn new(initial_ph: f64, salt_fraction: f64) -> Self {
+        Self {
+            initial_ph,
+            salt_fraction,
+            log_population: 1e3f64.ln(),
+            acid_g_per_l: 0.0,
+        }
+    }
+
+    /// Start from a SID composition, assuming pH 6.2 when none is recorded
+    pub fn from_composition(composition: &Composition, salt_fraction: f64) -> Self {
+        Self::new(composition.ph.unwrap_or(6.2), salt_fraction)
+    }
+
+    fn ph_for(&self, acid_g_per_l: f64) -> f64 {
+        let drop = PH_SLOPE * (1.0 + acid_g_per_l.max(0.0) / BUFFER_G_PER_L).log10();
+        (self.initial_ph - drop).max(MIN_GROWTH_PH.min(self.initial_ph))
+    }
+
+    /// Ferment at `temperature_c` for `dt` seconds; the state is unchanged if integration fails
+    pub fn advance(&mut self, temperature_c: f64, dt: f64) -> Result<(), IntegrationError> {
+        let system = LacticSystem {
+            ferment: self,
+            temperature_factor: Culture::LacticAcidBacteria
+                .cardinal_temperatures()
+                .factor(temperature_c),
+        };
+        let mut y = [self.log_population, self.acid_g_per_l];
+        Rk45::new(1e-6, 1e-9).integrate(&system, 0.0, dt, &mut y)?;
+        self.log_population = y[0];
+        self.acid_g_per_l = y[1];
+        Ok(())
+    }
+
+    /// Current pH, for `WaitUntil(brine.state.ph <= ..)`
+    pub fn ph(&self) -> f64 {
+        self.ph_for(self.acid_g_per_l)
+    }
+
+    pub fn acid_g_per_l(&self) -> f64 {
+        self.acid_g_per_l
+    }
+
+    /// Population, CFU/g
+    pub fn population(&self) -> f64 {
+        self.log_population.exp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: f64 = 3600.0;
+    const DAY: f64 = 24.0 * HOUR;
+
+    fn bread_dough() -> DoughFermentation {
+        // 500 g flour, 330 g water, 10 g fresh yeast
+        DoughFermentation::new(840.0, 10.0)
+    }
+
+    #[test]
+    fn test_cardinal_model_shape() {
+        let yeast = Culture::BakersYeast.cardinal_temperatures();
+        assert_eq!(yeast.factor(2.0), 0.0);
+        assert_eq!(yeast.factor(45.0), 0.0);
+        assert!((yeast.factor(35.0) - 1.0).abs() < 1e-12);
+        assert!(yeast.factor(25.0) < yeast.factor(30.0));
+        assert!(yeast.factor(42.0) < yeast.factor(35.0));
+    }
+
+    #[test]
+    fn test_dough_doubles_in_about_an_hour() {
+        let time = bread_dough().time_to_rise(2.0, 27.0, 4.0 * HOUR).unwrap();
+        assert!(time > 40.0 * 60.0 && time < 2.0 * HOUR, "{time}s");
+    }
+
+    #[test]
+    fn test_cold_retard_is_slow() {
+        let warm = bread_dough().time_to_rise(2.0, 27.0, DAY).unwrap();
+        let cool = bread_dough().time_to_rise(2.0, 18.0, DAY).unwrap();
+        assert!(cool > warm);
+
+        let mut fridge = bread_dough();
+        fridge.advance(4.0, 2.0 * HOUR).unwrap();
+        assert!(fridge.rise_ratio() < 1.2);
+    }
+
+    #[test]
+    fn test_dough_rise_is_bounded() {
+        let mut dough = bread_dough();
+        dough.advance(30.0, DAY).unwrap();
+        assert!(dough.rise_ratio() <= 1.0 + MAX_EXTRA_RISE);
+        assert!(dough.volume_ml() > dough.initial_volume_ml);
+    }
+
+    #[test]
+    fn test_sauerkraut_acidifies() {
+        let mut kraut = LacticFermentation::new(6.2, 0.02);
+        kraut.advance(20.0, 12.0 * HOUR).unwrap();
+        assert!(kraut.ph() > 5.5);
+        kraut.advance(20.0, 7.0 * DAY).unwrap();
+        assert!(kraut.ph() < 4.0, "pH {}", kraut.ph());
+        assert!(kraut.ph() >= MIN_GROWTH_PH);
+        assert!(kraut.population() <= MAX_POPULATION * 1.001);
+
+        let ph = kraut.ph();
+        assert!(kraut.advance(f64::NAN, HOUR).is_err());
+        assert_eq!(kraut.ph(), ph);
+    }
+
+    #[test]
+    fn test_salt_slows_fermentation() {
+        let mut light = LacticFermentation::new(6.2, 0.02);
+        let mut heavy = LacticFermentation::new(6.2, 0.06);
+        light.advance(20.0, 4.0 * DAY).unwrap();
+        heavy.advance(20.0, 4.0 * DAY).unwrap();
+        assert!(heavy.ph() > light.ph());
+    }
+
+    #[test]
+    fn test_ph_starts_from_composition() {
+        let cucumber = Composition {
+            water: 95.0,
+            protein: 0.7,
+            total_fat: 0.1,
+            saturated_fat: 0.0,
+            carbohydrates: 3.6,
+            fiber: 0.5,
+            sugar: 1.7,
+            ph: Some(5.8),
+            minerals: Default::default(),
+            vitamins: Default::default(),
+        };
+        let pickle = LacticFermentation::from_composition(&cucumber, 0.05);
+        assert_eq!(pickle.ph(), 5.8);
+        assert_eq!(
+            Culture::for_process(&ProcessType::Pickle),
+            Some(Culture::LacticAcidBacteria)
+        );
+    }
+}
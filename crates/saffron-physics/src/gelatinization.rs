@@ -0,0 +1,466 @@
+//! # Starch Gelatinization
+//!
+//! Two models for starch cooking:
+//!
+//! - **Immersion** (pasta, rice): water diffuses in from the surface. Uptake is
+//!   Fickian, with an Arrhenius diffusivity gated by the gelatinization onset
+//!   (~62°C); below it granules swell very little. The thermal history is
+//!   collapsed into a single diffusion exposure `θ = ∫ D(T) dt`, so the
+//!   classic Crank series solutions apply to any temperature history.
+//! - **In-situ** (doughs, batters): the water is already present and starch
+//!   gelatinizes by first-order kinetics, limited by how much water is available.
+//!
+//! Hydration at the center of the piece decides the texture: a thin
+//! ungelatinized core is al dente.
+
+use crate::conduction::Geometry;
+use crate::denaturation::ArrheniusKinetics;
+use saffron_ast::{Expr, Param, TextureState};
+use serde::{Deserialize, Serialize};
+
+/// Onset temperature of wheat/rice starch gelatinization, °C
+const GELATINIZATION_ONSET_C: f64 = 62.0;
+
+/// First positive roots of J0, and J1 evaluated at each root
+const BESSEL_J0_ROOTS: [(f64, f64); 5] = [
+    (2.404_826, 0.519_147),
+    (5.520_078, -0.340_265),
+    (8.653_728, 0.271_452),
+    (11.791_534, -0.232_461),
+    (14.930_918, 0.206_546),
+];
+
+/// Number of series terms; at `SHORT_TIME_FOURIER` the last one is below 1e-30
+const SERIES_TERMS: usize = 20;
+
+/// Below this Fourier number the short-time solutions are used instead of the
+/// series; both agree to within 1e-4 here
+const SHORT_TIME_FOURIER: f64 = 0.02;
+
+/// `n`th positive root of J0 (from 0) with J1 evaluated there: tabulated for
+/// the first roots, McMahon's asymptotic expansion beyond
+fn bessel_j0_root(n: usize) -> (f64, f64) {
+    if let Some(&root) = BESSEL_J0_ROOTS.get(n) {
+        return root;
+    }
+    let beta = (n as f64 + 0.75) * std::f64::consts::PI;
+    let root = beta + 1.0 / (8.0 * beta) - 31.0 / (384.0 * beta.powi(3))
+        + 3779.0 / (15_360.0 * beta.powi(5));
+    let sign = (-1.0f64).powi(n as i32);
+    let j1 =
+        sign * (2.0 / (std::f64::consts::PI * root)).sqrt() * (1.0 + 3.0 / (16.0 * root * root));
+    (root, j1)
+}
+
+/// Shape of a starch product, with its dry dimension
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StarchShape {
+    Spaghetti,
+    Linguine,
+    Fettuccine,
+    Penne,
+    Lasagna,
+    LongGrainRice,
+    ShortGrainRice,
+    /// Arbitrary geometry with half-thickness (slab) or radius, in meters
+    Custom {
+        geometry: Geometry,
+        half_thickness_m: f64,
+    },
+}
+
+impl StarchShape {
+    /// Diffusion geometry
+    pub fn geometry(&self) -> Geometry {
+        match self {
+            StarchShape::Spaghetti | StarchShape::LongGrainRice | StarchShape::ShortGrainRice => {
+                Geometry::Cylinder
+            }
+            // Penne walls behave like a thin sheet
+            StarchShape::Linguine
+            | StarchShape::Fettuccine
+            | StarchShape::Penne
+            | StarchShape::Lasagna => Geometry::Slab,
+            StarchShape::Custom { geometry, .. } => *geometry,
+        }
+    }
+
+    /// Dry radius (cylinder) or half-thickness (slab), in meters
+    pub fn half_thickness_m(&self) -> f64 {
+        match self {
+            StarchShape::Spaghetti => 0.9e-3,
+            StarchShape::Linguine => 0.6e-3,
+            StarchShape::Fettuccine => 0.55e-3,
+            StarchShape::Penne => 0.6e-3,
+            StarchShape::Lasagna => 0.65e-3,
+            StarchShape::LongGrainRice => 0.9e-3,
+            StarchShape::ShortGrainRice => 1.3e-3,
+            StarchShape::Custom {
+                half_thickness_m, ..
+            } => *half_thickness_m,
+        }
+    }
+
+    /// Match an enum variant name from a recipe (`.Spaghetti`, `.LongGrain`)
+    pub fn from_variant(variant: &str) -> Option<Self> {
+        match variant {
+            "Spaghetti" => Some(StarchShape::Spaghetti),
+            "Linguine" => Some(StarchShape::Linguine),
+            "Fettuccine" => Some(StarchShape::Fettuccine),
+            "Penne" => Some(StarchShape::Penne),
+            "Lasagna" => Some(StarchShape::Lasagna),
+            "LongGrain" | "Basmati" | "Jasmine" => Some(StarchShape::LongGrainRice),
+            "ShortGrain" | "Arborio" | "Sushi" => Some(StarchShape::ShortGrainRice),
+            _ => None,
+        }
+    }
+
+    /// Read the `type:` param of a `Pasta(...)` or `Rice(...)` declaration
+    pub fn from_params(params: &[Param]) -> Option<Self> {
+        params
+            .iter()
+            .find(|p| p.name == "type")
+            .and_then(|p| match &p.value {
+                Expr::EnumVariant { variant, .. } => Self::from_variant(variant),
+                _ => None,
+            })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Immersion cooking
+// ---------------------------------------------------------------------------
+
+/// Water uptake parameters for a starch product cooked in water
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HydrationModel {
+    pub shape: StarchShape,
+    /// Moisture of the dry product, % wet basis
+    pub dry_moisture_pct: f64,
+    /// Moisture once fully hydrated, % wet basis
+    pub equilibrium_moisture_pct: f64,
+    /// Effective water diffusivity above the gelatinization onset
+    pub diffusion: ArrheniusKinetics, // reference_rate in m^2/s
+}
+
+impl HydrationModel {
+    /// Durum wheat pasta (D ≈ 3e-10 m²/s at 100°C)
+    pub fn pasta(shape: StarchShape) -> Self {
+        Self {
+            shape,
+            dry_moisture_pct: 10.0,
+            equilibrium_moisture_pct: 70.0,
+            diffusion: ArrheniusKinetics {
+                activation_energy: 40_000.0,
+                reference_temp_c: 100.0,
+                reference_rate: 3.0e-10,
+            },
+        }
+    }
+
+    /// Milled white rice (D ≈ 2e-10 m²/s at 100°C)
+    pub fn rice(shape: StarchShape) -> Self {
+        Self {
+            shape,
+            dry_moisture_pct: 12.0,
+            equilibrium_moisture_pct: 68.0,
+            diffusion: ArrheniusKinetics {
+                activation_energy: 40_000.0,
+                reference_temp_c: 100.0,
+                reference_rate: 2.0e-10,
+            },
+        }
+    }
+
+    /// Effective diffusivity at `temperature_c`, in m²/s
+    pub fn diffusivity(&self, temperature_c: f64) -> f64 {
+        let gate = 1.0 / (1.0 + (-(temperature_c - GELATINIZATION_ONSET_C) / 2.0).exp());
+        self.diffusion.rate(temperature_c) * gate
+    }
+}
+
+/// Hydration progress of a starch product in water
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HydrationState {
+    pub model: HydrationModel,
+    /// Accumulated `∫ D(T) dt`, m²
+    exposure_m2: f64,
+}
+
+impl HydrationState {
+    /// Dry product, not yet in water
+    pub fn new(model: HydrationModel) -> Self {
+        Self {
+            model,
+            exposure_m2: 0.0,
+        }
+    }
+
+    /// Cook in water at `temperature_c` for `dt` seconds
+    pub fn advance(&mut self, temperature_c: f64, dt: f64) {
+        self.exposure_m2 += self.model.diffusivity(temperature_c) * dt;
+    }
+
+    /// Integrate over a `(time_s, water_temperature_c)` history
+    pub fn integrate(&mut self, history: &[(f64, f64)]) {
+        for pair in history.windows(2) {
+            let (t0, temp0) = pair[0];
+            let (t1, temp1) = pair[1];
+            self.advance((temp0 + temp1) / 2.0, t1 - t0);
+        }
+    }
+
+    /// Mass-transfer Fourier number `θ / a²`
+    pub fn fourier(&self) -> f64 {
+        let a = self.model.shape.half_thickness_m();
+        self.exposure_m2 / (a * a)
+    }
+
+    /// Share of the equilibrium water uptake reached (0.0–1.0)
+    pub fn uptake_fraction(&self) -> f64 {
+        let fo = self.fourier();
+        if fo <= 0.0 {
+            return 0.0;
+        }
+        if fo < SHORT_TIME_FOURIER {
+            // Short-time expansions; the series converge too slowly here
+            let pi = std::f64::consts::PI;
+            return match self.model.shape.geometry() {
+                Geometry::Slab => 2.0 * (fo / pi).sqrt(),
+                Geometry::Cylinder => 4.0 * (fo / pi).sqrt() - fo - (fo.powi(3) / pi).sqrt() / 3.0,
+                Geometry::Sphere => 6.0 * (fo / pi).sqrt() - 3.0 * fo,
+            };
+        }
+        let remaining: f64 = match self.model.shape.geometry() {
+            Geometry::Slab => (0..SERIES_TERMS)
+                .map(|n| {
+                    let l = (2 * n + 1) as f64 * std::f64::consts::PI / 2.0;
+                    2.0 / (l * l) * (-l * l * fo).exp()
+                })
+                .sum(),
+            Geometry::Cylinder => (0..SERIES_TERMS)
+                .map(|n| {
+                    let (a, _) = bessel_j0_root(n);
+                    4.0 / (a * a) * (-a * a * fo).exp()
+                })
+                .sum(),
+            Geometry::Sphere => (1..=SERIES_TERMS)
+                .map(|n| {
+                    let l = n as f64 * std::f64::consts::PI;
+                    6.0 / (l * l) * (-l * l * fo).exp()
+                })
+                .sum(),
+        };
+        (1.0 - remaining).clamp(0.0, 1.0)
+    }
+
+    /// Hydration at the center of the piece, relative to equilibrium (0.0–1.0)
+    pub fn core_hydration(&self) -> f64 {
+        let fo = self.fourier();
+        if fo < SHORT_TIME_FOURIER {
+            // Water has not reached the center yet (erfc(1/(2√Fo)) < 1e-6)
+            return 0.0;
+        }
+        let remaining: f64 = match self.model.shape.geometry() {
+            Geometry::Slab => (0..SERIES_TERMS)
+                .map(|n| {
+                    let l = (2 * n + 1) as f64 * std::f64::consts::PI / 2.0;
+                    let sign = if n % 2 == 0 { 1.0 } else { -1.0 };
+                    sign * 2.0 / l * (-l * l * fo).exp()
+                })
+                .sum(),
+            Geometry::Cylinder => (0..SERIES_TERMS)
+                .map(|n| {
+                    let (a, j1) = bessel_j0_root(n);
+                    2.0 / (a * j1) * (-a * a * fo).exp()
+                })
+                .sum(),
+            Geometry::Sphere => (1..=SERIES_TERMS)
+                .map(|n| {
+                    let l = n as f64 * std::f64::consts::PI;
+                    let sign = if n % 2 == 1 { 1.0 } else { -1.0 };
+                    sign * 2.0 * (-l * l * fo).exp()
+                })
+                .sum(),
+        };
+        (1.0 - remaining).clamp(0.0, 1.0)
+    }
+
+    /// Current moisture content, % wet basis
+    pub fn moisture_pct(&self) -> f64 {
+        let dry = self.model.dry_moisture_pct / 100.0;
+        let eq = self.model.equilibrium_moisture_pct / 100.0;
+        // Interpolate on a dry basis (g water / g solids), where uptake is linear
+        let x_dry = dry / (1.0 - dry);
+        let x_eq = eq / (1.0 - eq);
+        let x = x_dry + (x_eq - x_dry) * self.uptake_fraction();
+        100.0 * x / (1.0 + x)
+    }
+
+    /// Water absorbed relative to full hydration, as a percentage
+    pub fn hydration_pct(&self) -> f64 {
+        100.0 * self.uptake_fraction()
+    }
+
+    /// Texture from the hydration of the core
+    pub fn texture(&self) -> TextureState {
+        let core = self.core_hydration();
+        if core < 0.3 {
+            TextureState::Undercooked
+        } else if core < 0.7 {
+            TextureState::AlDente
+        } else if core < 0.95 {
+            TextureState::Tender
+        } else {
+            TextureState::Mushy
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// In-situ gelatinization (doughs, batters)
+// ---------------------------------------------------------------------------
+
+/// First-order gelatinization of starch that already holds its water
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DoughGelatinization {
+    pub kinetics: ArrheniusKinetics,
+    /// Highest reachable degree, limited by available water (0.0–1.0)
+    pub max_degree: f64,
+    degree: f64,
+}
+
+impl DoughGelatinization {
+    /// Dough with `moisture_pct` water (wet basis). Full gelatinization needs
+    /// roughly 60% moisture; drier doughs stay partially crystalline.
+    pub fn new(moisture_pct: f64) -> Self {
+        Self {
+            kinetics: ArrheniusKinetics {
+                activation_energy: 120_000.0,
+                reference_temp_c: 75.0,
+                reference_rate: 0.02,
+            },
+            max_degree: (moisture_pct / 60.0).clamp(0.0, 1.0),
+            degree: 0.0,
+        }
+    }
+
+    /// Bake at an internal `temperature_c` for `dt` seconds
+    pub fn advance(&mut self, temperature_c: f64, dt: f64) {
+        let k = self.kinetics.rate(temperature_c);
+        self.degree = self.max_degree - (self.max_degree - self.degree) * (-k * dt).exp();
+    }
+
+    /// Degree of gelatinization (0.0–1.0)
+    pub fn degree(&self) -> f64 {
+        self.degree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boil(model: HydrationModel, minutes: f64) -> HydrationState {
+        let mut state = HydrationState::new(model);
+        state.advance(100.0, minutes * 60.0);
+        state
+    }
+
+    #[test]
+    fn test_spaghetti_texture_over_time() {
+        let spaghetti = HydrationModel::pasta(StarchShape::Spaghetti);
+        assert_eq!(boil(spaghetti, 5.0).texture(), TextureState::Undercooked);
+        assert_eq!(boil(spaghetti, 9.0).texture(), TextureState::AlDente);
+        assert_eq!(boil(spaghetti, 15.0).texture(), TextureState::Tender);
+        assert_eq!(boil(spaghetti, 40.0).texture(), TextureState::Mushy);
+    }
+
+    #[test]
+    fn test_thin_pasta_cooks_faster() {
+        let spaghetti = boil(HydrationModel::pasta(StarchShape::Spaghetti), 6.0);
+        let linguine = boil(HydrationModel::pasta(StarchShape::Linguine), 6.0);
+        assert!(linguine.core_hydration() > spaghetti.core_hydration());
+    }
+
+    #[test]
+    fn test_cold_soak_barely_hydrates() {
+        let mut soaked = HydrationState::new(HydrationModel::pasta(StarchShape::Spaghetti));
+        soaked.advance(20.0, 600.0);
+        assert_eq!(soaked.texture(), TextureState::Undercooked);
+        assert!(soaked.hydration_pct() < 5.0);
+    }
+
+    #[test]
+    fn test_moisture_bounds() {
+        let model = HydrationModel::pasta(StarchShape::Penne);
+        let dry = HydrationState::new(model);
+        assert!((dry.moisture_pct() - 10.0).abs() < 1e-9);
+        let done = boil(model, 120.0);
+        assert!((done.moisture_pct() - 70.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_core_lags_average() {
+        for shape in [
+            StarchShape::Spaghetti,
+            StarchShape::Lasagna,
+            StarchShape::Custom {
+                geometry: Geometry::Sphere,
+                half_thickness_m: 1e-3,
+            },
+        ] {
+            let state = boil(HydrationModel::pasta(shape), 4.0);
+            assert!(state.core_hydration() < state.uptake_fraction());
+        }
+    }
+
+    #[test]
+    fn test_short_time_matches_series() {
+        for geometry in [Geometry::Slab, Geometry::Cylinder, Geometry::Sphere] {
+            let shape = StarchShape::Custom {
+                geometry,
+                half_thickness_m: 1e-3,
+            };
+            let at = |fo: f64| {
+                let mut state = HydrationState::new(HydrationModel::pasta(shape));
+                state.exposure_m2 = fo * 1e-6;
+                state
+            };
+            let below = at(SHORT_TIME_FOURIER * (1.0 - 1e-9));
+            let above = at(SHORT_TIME_FOURIER);
+            assert!(
+                (below.uptake_fraction() - above.uptake_fraction()).abs() < 1e-4,
+                "{geometry:?}"
+            );
+            assert!(above.core_hydration().abs() < 1e-4, "{geometry:?}");
+        }
+    }
+
+    #[test]
+    fn test_bessel_roots_extend_table() {
+        // Sixth root of J0 and J1 there, from Abramowitz & Stegun table 9.5
+        let (root, j1) = bessel_j0_root(5);
+        assert!((root - 18.071_064).abs() < 1e-5);
+        assert!((j1 + 0.187_729).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_dough_limited_by_water() {
+        let mut bread = DoughGelatinization::new(40.0);
+        let mut batter = DoughGelatinization::new(70.0);
+        bread.advance(98.0, 1200.0);
+        batter.advance(98.0, 1200.0);
+        assert!((bread.degree() - 40.0 / 60.0).abs() < 1e-3);
+        assert!((batter.degree() - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_shape_from_variant() {
+        assert_eq!(
+            StarchShape::from_variant("Spaghetti"),
+            Some(StarchShape::Spaghetti)
+        );
+        assert_eq!(StarchShape::from_variant("Ravioli"), None);
+    }
+}
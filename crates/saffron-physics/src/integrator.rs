@@ -0,0 +1,527 @@
+//! # Time Integration
+//!
+//! Shared ODE integrators for the physics models:
+//!
+//! - [`ImplicitEuler`] — first order, unconditionally stable. Never overshoots
+//!   an equilibrium on relaxation problems, whatever the step size, so it is
+//!   the default for user-chosen `dt`.
+//! - [`Rk45`] — Dormand–Prince 5(4) with embedded error control, for accurate
+//!   curves where the step size is picked automatically.
+//!
+//! A model describes itself through [`OdeSystem`]; the integrators work on
+//! plain `&mut [f64]` state vectors.
+
+use thiserror::Error;
+
+/// A system of ordinary differential equations `dy/dt = f(t, y)`
+pub trait OdeSystem {
+    /// Number of state variables
+    fn dimension(&self) -> usize;
+
+    /// Write `f(t, y)` into `dydt`
+    fn derivative(&self, t: f64, y: &[f64], dydt: &mut [f64]);
+
+    /// Write the Jacobian `∂f/∂y` into `jacobian` (row-major, `n × n`).
+    ///
+    /// The default uses forward differences; override it when an analytic
+    /// form is cheap.
+    fn jacobian(&self, t: f64, y: &[f64], jacobian: &mut [f64]) {
+        let n = self.dimension();
+        let mut base = vec![0.0; n];
+        let mut shifted = vec![0.0; n];
+        let mut probe = y.to_vec();
+        self.derivative(t, y, &mut base);
+        for j in 0..n {
+            let h = 1e-7 * y[j].abs().max(1.0);
+            probe[j] = y[j] + h;
+            self.derivative(t, &probe, &mut shifted);
+            probe[j] = y[j];
+            for i in 0..n {
+                jacobian[i * n + j] = (shifted[i] - base[i]) / h;
+            }
+        }
+    }
+}
+
+/// Integration failures
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum IntegrationError {
+    #[error("Step size fell below {min_dt}s at t = {t}s")]
+    StepSizeUnderflow { t: f64, min_dt: f64 },
+
+    #[error("Newton iteration did not converge at t = {t}s")]
+    NewtonDiverged { t: f64 },
+
+    #[error("State became non-finite at t = {t}s")]
+    NonFinite { t: f64 },
+
+    #[error("Singular Jacobian at t = {t}s")]
+    SingularJacobian { t: f64 },
+
+    #[error("Step size {dt}s is not a positive finite number")]
+    InvalidStepSize { dt: f64 },
+}
+
+/// Reject step sizes that would never advance time
+pub(crate) fn check_step_size(dt: f64) -> Result<(), IntegrationError> {
+    if dt > 0.0 && dt.is_finite() {
+        Ok(())
+    } else {
+        // Also catches NaN
+        Err(IntegrationError::InvalidStepSize { dt })
+    }
+}
+
+/// Work done by an integration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IntegrationStats {
+    pub accepted_steps: usize,
+    pub rejected_steps: usize,
+    pub derivative_evaluations: usize,
+}
+
+/// A time integrator
+pub trait Integrator {
+    /// Advance `y` from `t0` to `t_end` in place
+    fn integrate<S: OdeSystem + ?Sized>(
+        &self,
+        system: &S,
+        t0: f64,
+        t_end: f64,
+        y: &mut [f64],
+    ) -> Result<IntegrationStats, IntegrationError>;
+}
+
+// ---------------------------------------------------------------------------
+// Implicit Euler
+// ---------------------------------------------------------------------------
+
+/// Backward Euler with a Newton solve per step
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImplicitEuler {
+    /// Largest step, s; the final step is shortened to land on `t_end`
+    pub dt: f64,
+    /// Newton convergence tolerance, relative to the state magnitude
+    pub tolerance: f64,
+    pub max_iterations: usize,
+}
+
+impl ImplicitEuler {
+    pub fn new(dt: f64) -> Self {
+        Self {
+            dt,
+            tolerance: 1e-10,
+            max_iterations: 50,
+        }
+    }
+
+    /// Solve `y1 = y0 + dt f(t1, y1)` for `y1`, starting from `y0`
+    fn solve_step<S: OdeSystem + ?Sized>(
+        &self,
+        system: &S,
+        t1: f64,
+        dt: f64,
+        y0: &[f64],
+        y1: &mut [f64],
+        stats: &mut IntegrationStats,
+    ) -> Result<(), IntegrationError> {
+        let n = y0.len();
+        let mut f = vec![0.0; n];
+        let mut jacobian = vec![0.0; n * n];
+        let mut residual = vec![0.0; n];
+        y1.copy_from_slice(y0);
+
+        for _ in 0..self.max_iterations {
+            system.derivative(t1, y1, &mut f);
+            system.jacobian(t1, y1, &mut jacobian);
+            stats.derivative_evaluations += 1;
+
+            // G(y1) = y1 - y0 - dt f(y1);  G' = I - dt J
+            for i in 0..n {
+                residual[i] = -(y1[i] - y0[i] - dt * f[i]);
+                for j in 0..n {
+                    let identity = if i == j { 1.0 } else { 0.0 };
+                    jacobian[i * n + j] = identity - dt * jacobian[i * n + j];
+                }
+            }
+            solve_linear(&mut jacobian, &mut residual, n)
+                .ok_or(IntegrationError::SingularJacobian { t: t1 })?;
+
+            let mut converged = true;
+            for i in 0..n {
+                y1[i] += residual[i];
+                if !y1[i].is_finite() {
+                    return Err(IntegrationError::NonFinite { t: t1 });
+                }
+                if residual[i].abs() > self.tolerance * y1[i].abs().max(1.0) {
+                    converged = false;
+                }
+            }
+            if converged {
+                return Ok(());
+            }
+        }
+        Err(IntegrationError::NewtonDiverged { t: t1 })
+    }
+}
+
+impl Integrator for ImplicitEuler {
+    fn integrate<S: OdeSystem + ?Sized>(
+        &self,
+        system: &S,
+        t0: f64,
+        t_end: f64,
+        y: &mut [f64],
+    ) -> Result<IntegrationStats, IntegrationError> {
+        check_step_size(self.dt)?;
+        let mut stats = IntegrationStats::default();
+        let mut t = t0;
+        let mut next = vec![0.0; y.len()];
+        while t < t_end {
+            let dt = self.dt.min(t_end - t);
+            self.solve_step(system, t + dt, dt, y, &mut next, &mut stats)?;
+            y.copy_from_slice(&next);
+            t += dt;
+            stats.accepted_steps += 1;
+        }
+        Ok(stats)
+    }
+}
+
+/// Gaussian elimination with partial pivoting; solution is left in `b`
+fn solve_linear(a: &mut [f64], b: &mut [f64], n: usize) -> Option<()> {
+    for col in 0..n {
+        let pivot =
+            (col..n).max_by(|&i, &j| a[i * n + col].abs().total_cmp(&a[j * n + col].abs()))?;
+        if a[pivot * n + col].abs() < 1e-300 {
+            return None;
+        }
+        if pivot != col {
+            for k in 0..n {
+                a.swap(col * n + k, pivot * n + k);
+            }
+            b.swap(col, pivot);
+        }
+        for row in col + 1..n {
+            let factor = a[row * n + col] / a[col * n + col];
+            for k in col..n {
+                a[row * n + k] -= factor * a[col * n + k];
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n).map(|k| a[row * n + k] * b[k]).sum();
+        b[row] = (b[row] - sum) / a[row * n + row];
+    }
+    Some(())
+}
+
+// ---------------------------------------------------------------------------
+// Dormand–Prince RK45
+// ---------------------------------------------------------------------------
+
+const C: [f64; 7] = [0.0, 1.0 / 5.0, 3.0 / 10.0, 4.0 / 5.0, 8.0 / 9.0, 1.0, 1.0];
+
+const A: [[f64; 6]; 7] = [
+    [0.0; 6],
+    [1.0 / 5.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [3.0 / 40.0, 9.0 / 40.0, 0.0, 0.0, 0.0, 0.0],
+    [44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0, 0.0, 0.0, 0.0],
+    [
+        19372.0 / 6561.0,
+        -25360.0 / 2187.0,
+        64448.0 / 6561.0,
+        -212.0 / 729.0,
+        0.0,
+        0.0,
+    ],
+    [
+        9017.0 / 3168.0,
+        -355.0 / 33.0,
+        46732.0 / 5247.0,
+        49.0 / 176.0,
+        -5103.0 / 18656.0,
+        0.0,
+    ],
+    [
+        35.0 / 384.0,
+        0.0,
+        500.0 / 1113.0,
+        125.0 / 192.0,
+        -2187.0 / 6784.0,
+        11.0 / 84.0,
+    ],
+];
+
+/// Difference between the 5th- and 4th-order weights
+const E: [f64; 7] = [
+    71.0 / 57600.0,
+    0.0,
+    -71.0 / 16695.0,
+    71.0 / 1920.0,
+    -17253.0 / 339200.0,
+    22.0 / 525.0,
+    -1.0 / 40.0,
+];
+
+/// Adaptive Dormand–Prince 5(4)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rk45 {
+    pub rtol: f64,
+    pub atol: f64,
+    pub initial_dt: f64,
+    pub min_dt: f64,
+    pub max_dt: f64,
+}
+
+impl Default for Rk45 {
+    fn default() -> Self {
+        Self {
+            rtol: 1e-6,
+            atol: 1e-6,
+            initial_dt: 1.0,
+            min_dt: 1e-9,
+            max_dt: f64::INFINITY,
+        }
+    }
+}
+
+impl Rk45 {
+    pub fn new(rtol: f64, atol: f64) -> Self {
+        Self {
+            rtol,
+            atol,
+            ..Self::default()
+        }
+    }
+}
+
+impl Integrator for Rk45 {
+    fn integrate<S: OdeSystem + ?Sized>(
+        &self,
+        system: &S,
+        t0: f64,
+        t_end: f64,
+        y: &mut [f64],
+    ) -> Result<IntegrationStats, IntegrationError> {
+        check_step_size(self.initial_dt)?;
+        check_step_size(self.min_dt)?;
+        let n = y.len();
+        let mut stats = IntegrationStats::default();
+        let mut k = vec![vec![0.0; n]; 7];
+        let mut stage = vec![0.0; n];
+        let mut t = t0;
+        let mut dt = self.initial_dt.min(self.max_dt);
+
+        system.derivative(t, y, &mut k[0]);
+        stats.derivative_evaluations += 1;
+
+        while t < t_end {
+            dt = dt.min(t_end - t);
+            if dt < self.min_dt && t_end - t > self.min_dt {
+                return Err(IntegrationError::StepSizeUnderflow {
+                    t,
+                    min_dt: self.min_dt,
+                });
+            }
+
+            for s in 1..7 {
+                for i in 0..n {
+                    stage[i] = y[i] + dt * (0..s).map(|j| A[s][j] * k[j][i]).sum::<f64>();
+                }
+                system.derivative(t + C[s] * dt, &stage, &mut k[s]);
+                stats.derivative_evaluations += 1;
+            }
+            // Stage 7 is evaluated at the 5th-order solution (FSAL), so `stage` holds y_new
+
+            let mut error = 0.0;
+            for i in 0..n {
+                let e = dt * (0..7).map(|j| E[j] * k[j][i]).sum::<f64>();
+                let scale = self.atol + self.rtol * y[i].abs().max(stage[i].abs());
+                error += (e / scale).powi(2);
+            }
+            let error = (error / n.max(1) as f64).sqrt();
+            if !error.is_finite() {
+                return Err(IntegrationError::NonFinite { t });
+            }
+
+            if error <= 1.0 {
+                t += dt;
+                y.copy_from_slice(&stage);
+                k.swap(0, 6);
+                stats.accepted_steps += 1;
+            } else {
+                stats.rejected_steps += 1;
+            }
+
+            let factor = if error == 0.0 {
+                5.0
+            } else {
+                (0.9 * error.powf(-0.2)).clamp(0.2, 5.0)
+            };
+            dt = (dt * factor).min(self.max_dt);
+        }
+        Ok(stats)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Reference systems
+// ---------------------------------------------------------------------------
+
+/// A lumped body relaxing toward a source (Newton's law of cooling)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NewtonCooling {
+    pub source_c: f64,
+    /// Heat transfer coefficient × area, W/K
+    pub conductance: f64,
+    /// Mass × specific heat, J/K
+    pub heat_capacity: f64,
+}
+
+impl NewtonCooling {
+    /// Relaxation rate, 1/s
+    fn rate(&self) -> f64 {
+        self.conductance / self.heat_capacity
+    }
+}
+
+impl OdeSystem for NewtonCooling {
+    fn dimension(&self) -> usize {
+        1
+    }
+
+    fn derivative(&self, _t: f64, y: &[f64], dydt: &mut [f64]) {
+        dydt[0] = self.rate() * (self.source_c - y[0]);
+    }
+
+    fn jacobian(&self, _t: f64, _y: &[f64], jacobian: &mut [f64]) {
+        jacobian[0] = -self.rate();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Undamped oscillator, `y'' = -y`
+    struct Oscillator;
+
+    impl OdeSystem for Oscillator {
+        fn dimension(&self) -> usize {
+            2
+        }
+
+        fn derivative(&self, _t: f64, y: &[f64], dydt: &mut [f64]) {
+            dydt[0] = y[1];
+            dydt[1] = -y[0];
+        }
+    }
+
+    fn water() -> NewtonCooling {
+        NewtonCooling {
+            source_c: 100.0,
+            conductance: 0.006,
+            heat_capacity: 0.25 * 4186.0,
+        }
+    }
+
+    #[test]
+    fn test_rk45_matches_exact_solution() {
+        let system = water();
+        let mut y = [20.0];
+        let stats = Rk45::new(1e-8, 1e-8)
+            .integrate(&system, 0.0, 600.0, &mut y)
+            .unwrap();
+        let exact = 100.0 - 80.0 * (-system.rate() * 600.0).exp();
+        assert!((y[0] - exact).abs() < 1e-5);
+        assert!(stats.accepted_steps > 0);
+    }
+
+    #[test]
+    fn test_rk45_oscillator_conserves_amplitude() {
+        let mut y = [1.0, 0.0];
+        Rk45::new(1e-9, 1e-9)
+            .integrate(&Oscillator, 0.0, 2.0 * std::f64::consts::PI, &mut y)
+            .unwrap();
+        assert!((y[0] - 1.0).abs() < 1e-6);
+        assert!(y[1].abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_rk45_rejects_steps_that_are_too_large() {
+        let mut y = [1.0, 0.0];
+        let stats = Rk45 {
+            initial_dt: 10.0,
+            ..Rk45::new(1e-8, 1e-8)
+        }
+        .integrate(&Oscillator, 0.0, 1.0, &mut y)
+        .unwrap();
+        assert!(stats.rejected_steps > 0);
+    }
+
+    #[test]
+    fn test_implicit_euler_uses_finite_difference_jacobian() {
+        let mut y = [1.0, 0.0];
+        ImplicitEuler::new(0.001)
+            .integrate(&Oscillator, 0.0, 1.0, &mut y)
+            .unwrap();
+        assert!((y[0] - 1f64.cos()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_rejects_non_positive_step_sizes() {
+        let system = water();
+        for dt in [0.0, -1.0, f64::NAN] {
+            let mut y = [20.0];
+            assert!(matches!(
+                ImplicitEuler::new(dt).integrate(&system, 0.0, 60.0, &mut y),
+                Err(IntegrationError::InvalidStepSize { .. })
+            ));
+            let rk45 = Rk45 {
+                initial_dt: dt,
+                ..Rk45::default()
+            };
+            assert!(matches!(
+                rk45.integrate(&system, 0.0, 60.0, &mut y),
+                Err(IntegrationError::InvalidStepSize { .. })
+            ));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn implicit_euler_never_overshoots(
+            start in -20.0f64..250.0,
+            source in -20.0f64..250.0,
+            dt in 1e-3f64..1e6,
+        ) {
+            let system = NewtonCooling { source_c: source, ..water() };
+            let (lo, hi) = (start.min(source), start.max(source));
+            let mut y = [start];
+            let mut previous = (start - source).abs();
+            for _ in 0..10 {
+                ImplicitEuler::new(dt).integrate(&system, 0.0, dt, &mut y).unwrap();
+                prop_assert!(y[0] >= lo - 1e-9 && y[0] <= hi + 1e-9);
+                let distance = (y[0] - source).abs();
+                prop_assert!(distance <= previous + 1e-9);
+                previous = distance;
+            }
+        }
+
+        #[test]
+        fn rk45_stays_within_bounds(
+            start in -20.0f64..250.0,
+            source in -20.0f64..250.0,
+            duration in 1.0f64..1e5,
+        ) {
+            let system = NewtonCooling { source_c: source, ..water() };
+            let (lo, hi) = (start.min(source), start.max(source));
+            let mut y = [start];
+            Rk45::default().integrate(&system, 0.0, duration, &mut y).unwrap();
+            prop_assert!(y[0] >= lo - 1e-3 && y[0] <= hi + 1e-3);
+        }
+    }
+}
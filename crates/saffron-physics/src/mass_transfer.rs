@@ -0,0 +1,417 @@
+//! # Mass Transfer During Cooking
+//!
+//! Tracks how an ingredient's composition changes as it cooks, so nutrition
+//! can be computed on the cooked food rather than the raw SID entry:
+//!
+//! - **Evaporation** — in dry heat the surface boils off water at a rate set
+//!   by the heat reaching it, slowing as a dry crust forms.
+//! - **Contraction** — muscle proteins shrink between ~40 and 80°C and squeeze
+//!   out water, in any cooking medium.
+//! - **Rendering** — fat melts and drips out of meat on a grill or in an oven.
+//! - **Oil uptake** — fried food takes up oil roughly in proportion to the
+//!   water it has lost (it fills the pores the steam left behind).
+//!
+//! Masses are tracked in grams; [`CookingState::composition`] converts back to
+//! a per-100 g `Composition`.
+
+use crate::evaporation::latent_heat_vaporization;
+use crate::integrator::{check_step_size, IntegrationError};
+use saffron_ast::ProcessType;
+use saffron_sid::Composition;
+use serde::{Deserialize, Serialize};
+
+/// Share of its water a lean muscle (20% protein) loses when fully contracted
+const MAX_CONTRACTION_LOSS: f64 = 0.25;
+
+/// Time constant for proteins to contract at a new temperature, s
+const CONTRACTION_TIME_S: f64 = 300.0;
+
+/// Longest internal step, s
+const MAX_STEP_S: f64 = 1.0;
+
+/// Most internal steps per call; longer calls take proportionally longer steps
+const MAX_SUBSTEPS: f64 = 100_000.0;
+
+/// Cooking methods with distinct mass transfer behaviour
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransferKind {
+    Fry,
+    DeepFry,
+    Roast,
+    Grill,
+    Boil,
+}
+
+impl TransferKind {
+    pub fn from_process(process: &ProcessType) -> Option<Self> {
+        match process {
+            ProcessType::Fry | ProcessType::Saute => Some(TransferKind::Fry),
+            ProcessType::DeepFry => Some(TransferKind::DeepFry),
+            ProcessType::Roast | ProcessType::Bake => Some(TransferKind::Roast),
+            ProcessType::Grill | ProcessType::Broil => Some(TransferKind::Grill),
+            ProcessType::Boil | ProcessType::Simmer | ProcessType::Poach => {
+                Some(TransferKind::Boil)
+            }
+            _ => None,
+        }
+    }
+
+    /// Heat transfer coefficient from the medium to a drying surface, W/(m^2*K)
+    fn drying_h(self) -> f64 {
+        match self {
+            TransferKind::DeepFry => 300.0,
+            TransferKind::Fry => 200.0,
+            TransferKind::Grill => 40.0,
+            TransferKind::Roast => 15.0,
+            // Submerged: the surface never dries
+            TransferKind::Boil => 0.0,
+        }
+    }
+
+    /// Grams of oil taken up per gram of water evaporated
+    fn oil_uptake_ratio(self) -> f64 {
+        match self {
+            TransferKind::DeepFry => 0.2,
+            TransferKind::Fry => 0.1,
+            _ => 0.0,
+        }
+    }
+
+    /// Share of the fat that renders out once fully heated
+    fn max_rendering(self) -> f64 {
+        match self {
+            TransferKind::Grill => 0.3,
+            TransferKind::Roast => 0.25,
+            TransferKind::Fry => 0.15,
+            TransferKind::Boil => 0.1,
+            // Rendered fat simply mixes with the frying oil
+            TransferKind::DeepFry => 0.0,
+        }
+    }
+}
+
+/// Conditions of one cooking process
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TransferProcess {
+    pub kind: TransferKind,
+    /// Oil, oven air, grill or water temperature, °C
+    pub medium_temp_c: f64,
+    /// Food surface exposed to the medium, m^2
+    pub surface_area_m2: f64,
+    /// Saturated share of the frying oil's fat (sunflower ≈ 0.1, lard ≈ 0.4)
+    pub oil_saturated_fraction: f64,
+}
+
+impl TransferProcess {
+    pub fn new(kind: TransferKind, medium_temp_c: f64, surface_area_m2: f64) -> Self {
+        Self {
+            kind,
+            medium_temp_c,
+            surface_area_m2,
+            oil_saturated_fraction: 0.1,
+        }
+    }
+
+    /// Build for a recipe process; `None` if the process moves no mass
+    pub fn for_process(
+        process: &ProcessType,
+        medium_temp_c: f64,
+        surface_area_m2: f64,
+    ) -> Option<Self> {
+        TransferKind::from_process(process).map(|k| Self::new(k, medium_temp_c, surface_area_m2))
+    }
+}
+
+/// A cooking ingredient, tracked as absolute masses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CookingState {
+    pub water_g: f64,
+    pub protein_g: f64,
+    pub fat_g: f64,
+    pub saturated_fat_g: f64,
+    pub carbohydrates_g: f64,
+    pub fiber_g: f64,
+    pub sugar_g: f64,
+    raw: Composition,
+    raw_mass_g: f64,
+    /// Protein contraction and fat rendering extent (0.0–1.0)
+    extent: f64,
+}
+
+impl CookingState {
+    /// `mass_g` of a raw ingredient with the given per-100 g composition
+    pub fn new(composition: &Composition, mass_g: f64) -> Self {
+        let scale = mass_g / 100.0;
+        Self {
+            water_g: composition.water * scale,
+            protein_g: composition.protein * scale,
+            fat_g: composition.total_fat * scale,
+            saturated_fat_g: composition.saturated_fat * scale,
+            carbohydrates_g: composition.carbohydrates * scale,
+            fiber_g: composition.fiber * scale,
+            sugar_g: composition.sugar * scale,
+            raw: composition.clone(),
+            raw_mass_g: mass_g,
+            extent: 0.0,
+        }
+    }
+
+    /// Mass not accounted for by the tracked components (ash, alcohol, ...)
+    fn other_g(&self) -> f64 {
+        let raw = &self.raw;
+        let tracked = raw.water + raw.protein + raw.total_fat + raw.carbohydrates;
+        (100.0 - tracked).max(0.0) * self.raw_mass_g / 100.0
+    }
+
+    pub fn mass_g(&self) -> f64 {
+        self.water_g + self.protein_g + self.fat_g + self.carbohydrates_g + self.other_g()
+    }
+
+    /// Cooked mass as a share of the raw mass
+    pub fn yield_fraction(&self) -> f64 {
+        self.mass_g() / self.raw_mass_g
+    }
+
+    /// Cook for `dt` seconds with the given core and surface temperatures
+    pub fn advance(
+        &mut self,
+        process: &TransferProcess,
+        core_temp_c: f64,
+        surface_temp_c: f64,
+        dt: f64,
+    ) -> Result<(), IntegrationError> {
+        if dt == 0.0 {
+            return Ok(());
+        }
+        check_step_size(dt)?;
+        // Crust formation makes drying nonlinear, so take short substeps
+        let steps = (dt / MAX_STEP_S).ceil().clamp(1.0, MAX_SUBSTEPS) as usize;
+        for _ in 0..steps {
+            self.step(process, core_temp_c, surface_temp_c, dt / steps as f64);
+        }
+        Ok(())
+    }
+
+    fn step(&mut self, process: &TransferProcess, core_temp_c: f64, surface_temp_c: f64, dt: f64) {
+        let kind = process.kind;
+        let raw_water = self.raw.water * self.raw_mass_g / 100.0;
+        let raw_fat = self.raw.total_fat * self.raw_mass_g / 100.0;
+
+        // Surface evaporation, limited by heat supply and a drying crust
+        let evaporated = if raw_water > 0.0 {
+            let heat_flux = kind.drying_h() * (process.medium_temp_c - 100.0).max(0.0);
+            let wet_surface = ((surface_temp_c - 90.0) / 10.0).clamp(0.0, 1.0);
+            let crust = (self.water_g / raw_water).powi(2);
+            let rate_g = heat_flux * process.surface_area_m2 / latent_heat_vaporization(100.0)
+                * 1000.0
+                * wet_surface
+                * crust;
+            (rate_g * dt).min(self.water_g)
+        } else {
+            0.0
+        };
+        self.water_g -= evaporated;
+
+        // Contraction and rendering follow the core temperature, and do not reverse
+        let target = ((core_temp_c - 40.0) / 40.0).clamp(0.0, 1.0);
+        let previous = self.extent;
+        if target > self.extent {
+            self.extent = target - (target - self.extent) * (-dt / CONTRACTION_TIME_S).exp();
+        }
+        let d_extent = self.extent - previous;
+
+        let protein_factor = (self.raw.protein / 20.0).min(1.0);
+        let squeezed = MAX_CONTRACTION_LOSS * protein_factor * raw_water * d_extent;
+        self.water_g = (self.water_g - squeezed).max(0.0);
+
+        let rendered = (kind.max_rendering() * raw_fat * d_extent).min(self.fat_g);
+        if self.fat_g > 0.0 {
+            self.saturated_fat_g -= self.saturated_fat_g * rendered / self.fat_g;
+        }
+        self.fat_g -= rendered;
+
+        let absorbed = kind.oil_uptake_ratio() * evaporated;
+        self.fat_g += absorbed;
+        self.saturated_fat_g += absorbed * process.oil_saturated_fraction;
+    }
+
+    /// Integrate over `(time_s, core_temp_c, surface_temp_c)` samples
+    pub fn integrate(
+        &mut self,
+        process: &TransferProcess,
+        history: &[(f64, f64, f64)],
+    ) -> Result<(), IntegrationError> {
+        for pair in history.windows(2) {
+            let (t0, core0, surface0) = pair[0];
+            let (t1, core1, surface1) = pair[1];
+            self.advance(
+                process,
+                (core0 + core1) / 2.0,
+                (surface0 + surface1) / 2.0,
+                t1 - t0,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Cooked composition per 100 g, for nutrition facts.
+    ///
+    /// Minerals and vitamins are assumed retained, so they concentrate as the
+    /// food loses mass. Nothing is left of a zero mass, so its composition is all zeros.
+    pub fn composition(&self) -> Composition {
+        let mass = self.mass_g();
+        let (per_100, concentration) = if mass > 0.0 {
+            (100.0 / mass, self.raw_mass_g / mass)
+        } else {
+            (0.0, 0.0)
+        };
+        let scale_map = |map: &std::collections::HashMap<String, f64>| {
+            map.iter()
+                .map(|(k, v)| (k.clone(), v * concentration))
+                .collect()
+        };
+        Composition {
+            water: self.water_g * per_100,
+            protein: self.protein_g * per_100,
+            total_fat: self.fat_g * per_100,
+            saturated_fat: self.saturated_fat_g * per_100,
+            carbohydrates: self.carbohydrates_g * per_100,
+            fiber: self.fiber_g * per_100,
+            sugar: self.sugar_g * per_100,
+            ph: self.raw.ph,
+            minerals: scale_map(&self.raw.minerals),
+            vitamins: scale_map(&self.raw.vitamins),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn composition(water: f64, protein: f64, fat: f64, carbs: f64) -> Composition {
+        Composition {
+            water,
+            protein,
+            total_fat: fat,
+            saturated_fat: fat * 0.3,
+            carbohydrates: carbs,
+            fiber: 0.0,
+            sugar: 0.0,
+            ph: None,
+            minerals: [("sodium".to_string(), 50.0)].into_iter().collect(),
+            vitamins: Default::default(),
+        }
+    }
+
+    /// Linear ramp of core temperature with the surface held at `surface`, sampled each minute
+    fn ramp(duration_s: f64, core_from: f64, core_to: f64, surface: f64) -> Vec<(f64, f64, f64)> {
+        let n = (duration_s / 60.0) as usize;
+        (0..=n)
+            .map(|i| {
+                let f = i as f64 / n as f64;
+                (
+                    f * duration_s,
+                    core_from + f * (core_to - core_from),
+                    surface,
+                )
+            })
+            .collect()
+    }
+
+    fn potato() -> Composition {
+        composition(79.0, 2.0, 0.1, 17.5)
+    }
+
+    fn chicken_breast() -> Composition {
+        composition(74.8, 22.5, 2.6, 0.0)
+    }
+
+    #[test]
+    fn test_deep_fried_potato_absorbs_oil() {
+        let mut fries = CookingState::new(&potato(), 100.0);
+        let process = TransferProcess::new(TransferKind::DeepFry, 180.0, 0.05);
+        fries
+            .integrate(&process, &ramp(300.0, 20.0, 99.0, 105.0))
+            .unwrap();
+
+        let cooked = fries.composition();
+        assert!(fries.yield_fraction() < 0.8);
+        assert!(cooked.water < 79.0);
+        assert!(cooked.total_fat > 5.0 && cooked.total_fat < 25.0);
+        assert!(cooked.saturated_fat < cooked.total_fat);
+    }
+
+    #[test]
+    fn test_roast_chicken_yield() {
+        let mut chicken = CookingState::new(&chicken_breast(), 200.0);
+        let process = TransferProcess::new(TransferKind::Roast, 200.0, 0.03);
+        chicken
+            .integrate(&process, &ramp(2400.0, 5.0, 74.0, 100.0))
+            .unwrap();
+
+        let yield_fraction = chicken.yield_fraction();
+        assert!(
+            yield_fraction > 0.6 && yield_fraction < 0.85,
+            "yield {yield_fraction}"
+        );
+        assert!(chicken.composition().protein > 22.5);
+        assert!(chicken.fat_g < 200.0 * 0.026);
+    }
+
+    #[test]
+    fn test_boiling_squeezes_water_without_adding_fat() {
+        let mut boiled = CookingState::new(&chicken_breast(), 200.0);
+        let process = TransferProcess::new(TransferKind::Boil, 100.0, 0.03);
+        boiled
+            .integrate(&process, &ramp(1800.0, 5.0, 80.0, 100.0))
+            .unwrap();
+        assert!(boiled.yield_fraction() < 0.95);
+        assert!(boiled.fat_g <= 200.0 * 0.026);
+    }
+
+    #[test]
+    fn test_cold_food_does_not_change() {
+        let mut raw = CookingState::new(&chicken_breast(), 150.0);
+        let process = TransferProcess::new(TransferKind::Grill, 250.0, 0.02);
+        raw.advance(&process, 20.0, 20.0, 600.0).unwrap();
+        assert!((raw.yield_fraction() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_minerals_concentrate() {
+        let mut chicken = CookingState::new(&chicken_breast(), 200.0);
+        let process = TransferProcess::new(TransferKind::Grill, 250.0, 0.03);
+        chicken
+            .integrate(&process, &ramp(900.0, 5.0, 70.0, 100.0))
+            .unwrap();
+        assert!(chicken.composition().minerals["sodium"] > 50.0);
+    }
+
+    #[test]
+    fn test_rejects_bad_step_sizes() {
+        let mut chicken = CookingState::new(&chicken_breast(), 200.0);
+        let process = TransferProcess::new(TransferKind::Grill, 250.0, 0.03);
+        for dt in [-1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                chicken.advance(&process, 70.0, 100.0, dt),
+                Err(IntegrationError::InvalidStepSize { .. })
+            ));
+        }
+        assert!((chicken.yield_fraction() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_zero_mass_composition() {
+        let empty = CookingState::new(&chicken_breast(), 0.0).composition();
+        assert_eq!(empty.water, 0.0);
+        assert_eq!(empty.total_fat, 0.0);
+        assert_eq!(empty.minerals["sodium"], 0.0);
+    }
+
+    #[test]
+    fn test_process_mapping() {
+        assert!(TransferProcess::for_process(&ProcessType::DeepFry, 180.0, 0.05).is_some());
+        assert!(TransferProcess::for_process(&ProcessType::Whisk, 20.0, 0.05).is_none());
+    }
+}
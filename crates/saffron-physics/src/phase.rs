@@ -0,0 +1,436 @@
+//! # Phase Transitions
+//!
+//! Enthalpy-based tracking of melting, freezing and boiling. Heat goes into
+//! enthalpy, and temperature and phase are derived from it, so latent heat is
+//! absorbed naturally: ice sits at 0°C until it has taken up its heat of
+//! fusion, and boiling water sits at its boiling point until it has taken up
+//! its heat of vaporization.
+//!
+//! Enthalpy is measured from the solid at its melting point:
+//!
+//! ```text
+//! H <  0                 solid,   T = T_m + H / (m c_s)
+//! 0 ≤ H ≤ m L_f          melting, T = T_m
+//! ...                    liquid,  then boiling at T_b, then gas
+//! ```
+
+use crate::evaporation::{boiling_point_c, latent_heat_vaporization};
+use saffron_ast::{IngredientCategory, Phase};
+use saffron_sid::IngredientEntry;
+use serde::{Deserialize, Serialize};
+
+/// Latent heat of fusion of ice, J/kg
+const LATENT_FUSION_WATER: f64 = 334_000.0;
+
+/// Typical latent heat of fusion of crystalline fat, J/kg
+const LATENT_FUSION_FAT: f64 = 150_000.0;
+
+/// Typical latent heat of vaporization of non-aqueous matter, J/kg
+const LATENT_VAPORIZATION_OTHER: f64 = 300_000.0;
+
+/// Specific heat of water vapor, J/(kg*K)
+const SPECIFIC_HEAT_VAPOR: f64 = 2_010.0;
+
+/// Thermophysical data needed to move a substance between phases
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhaseModel {
+    /// Melting/freezing point; `None` if the substance has no tracked solid–liquid transition
+    pub melting_point_c: Option<f64>,
+    /// Boiling point; `None` if it decomposes before boiling
+    pub boiling_point_c: Option<f64>,
+    pub latent_fusion: f64,       // J/kg
+    pub latent_vaporization: f64, // J/kg
+    pub specific_heat_solid: f64, // J/(kg*K)
+    pub specific_heat_liquid: f64,
+    pub specific_heat_gas: f64,
+    /// Phase between the transitions when there is no melting point (e.g. flour stays `Solid`)
+    pub base_phase: Phase,
+}
+
+impl PhaseModel {
+    /// Pure water at sea level
+    pub fn water() -> Self {
+        Self {
+            melting_point_c: Some(0.0),
+            boiling_point_c: Some(100.0),
+            latent_fusion: LATENT_FUSION_WATER,
+            latent_vaporization: latent_heat_vaporization(100.0),
+            specific_heat_solid: 2_100.0,
+            specific_heat_liquid: 4_186.0,
+            specific_heat_gas: SPECIFIC_HEAT_VAPOR,
+            base_phase: Phase::Liquid,
+        }
+    }
+
+    /// Water at an ambient pressure in kPa (see [`crate::evaporation::pressure_at_altitude_kpa`])
+    pub fn water_at_pressure(pressure_kpa: f64) -> Self {
+        let boiling = boiling_point_c(pressure_kpa);
+        Self {
+            boiling_point_c: Some(boiling),
+            latent_vaporization: latent_heat_vaporization(boiling),
+            ..Self::water()
+        }
+    }
+
+    /// Build from SID physical properties and composition.
+    ///
+    /// Latent heats scale with the water (or, for fats, fat) content; ice and
+    /// crystalline fat have roughly half the specific heat of their liquids.
+    pub fn from_sid(entry: &IngredientEntry) -> Self {
+        let water = entry.composition.water / 100.0;
+        let fat = entry.composition.total_fat / 100.0;
+        let is_fat = entry.category == IngredientCategory::Fat;
+        let is_liquid = entry.category == IngredientCategory::Liquid || is_fat;
+
+        let specific_heat_liquid = entry
+            .physical
+            .specific_heat_j_per_g_k
+            .map(|cp| cp * 1000.0)
+            .unwrap_or(1_500.0 + 2_686.0 * water);
+        let latent_fusion = if is_fat {
+            LATENT_FUSION_FAT * fat
+        } else {
+            LATENT_FUSION_WATER * water
+        };
+
+        Self {
+            melting_point_c: entry.physical.freezing_point_celsius,
+            boiling_point_c: entry.physical.boiling_point_celsius,
+            latent_fusion,
+            latent_vaporization: latent_heat_vaporization(100.0) * water
+                + LATENT_VAPORIZATION_OTHER * (1.0 - water),
+            specific_heat_solid: specific_heat_liquid * 0.5,
+            specific_heat_liquid,
+            specific_heat_gas: SPECIFIC_HEAT_VAPOR,
+            base_phase: if is_liquid {
+                Phase::Liquid
+            } else {
+                Phase::Solid
+            },
+        }
+    }
+
+    /// Reference point for enthalpy: the melting point, or 0°C without one
+    fn reference_c(&self) -> f64 {
+        self.melting_point_c.unwrap_or(0.0)
+    }
+
+    /// Specific heat below the melting point
+    fn low_specific_heat(&self) -> f64 {
+        if self.melting_point_c.is_some() {
+            self.specific_heat_solid
+        } else {
+            self.specific_heat_liquid
+        }
+    }
+
+    /// Specific enthalpy (J/kg) at `temperature_c`, taking the lower-enthalpy
+    /// end of any transition plateau
+    pub fn enthalpy_at(&self, temperature_c: f64) -> f64 {
+        let reference = self.reference_c();
+        if temperature_c <= reference {
+            return self.low_specific_heat() * (temperature_c - reference);
+        }
+        let fusion = if self.melting_point_c.is_some() {
+            self.latent_fusion
+        } else {
+            0.0
+        };
+        match self.boiling_point_c {
+            Some(tb) if temperature_c > tb => {
+                fusion
+                    + self.specific_heat_liquid * (tb - reference)
+                    + self.latent_vaporization
+                    + self.specific_heat_gas * (temperature_c - tb)
+            }
+            _ => fusion + self.specific_heat_liquid * (temperature_c - reference),
+        }
+    }
+
+    /// Latent heat of the plateau at `temperature_c`, zero away from a transition
+    fn plateau_at(&self, temperature_c: f64) -> f64 {
+        if self.melting_point_c == Some(temperature_c) {
+            self.latent_fusion
+        } else if self.boiling_point_c == Some(temperature_c) {
+            self.latent_vaporization
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Thermodynamic state of a lump of one substance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhaseState {
+    pub model: PhaseModel,
+    pub mass_kg: f64,
+    /// Specific enthalpy relative to the model's reference, J/kg
+    specific_enthalpy: f64,
+}
+
+/// Position of a specific enthalpy within the phase diagram
+enum Region {
+    BelowMelting,
+    Melting(f64),
+    Liquid,
+    Boiling(f64),
+    Gas,
+}
+
+impl PhaseState {
+    pub fn new(model: PhaseModel, mass_kg: f64, temperature_c: f64) -> Self {
+        let specific_enthalpy = model.enthalpy_at(temperature_c);
+        Self {
+            model,
+            mass_kg,
+            specific_enthalpy,
+        }
+    }
+
+    /// Total enthalpy relative to the reference state, J
+    pub fn enthalpy_j(&self) -> f64 {
+        self.specific_enthalpy * self.mass_kg
+    }
+
+    /// Add (or with a negative value, remove) heat in joules
+    pub fn add_heat(&mut self, joules: f64) {
+        if self.mass_kg > 0.0 {
+            self.specific_enthalpy += joules / self.mass_kg;
+        }
+    }
+
+    /// Exchange heat with surroundings at `ambient_c` through `conductance` (W/K) for `dt` seconds.
+    ///
+    /// The heat transferred is capped so the state never passes the ambient
+    /// temperature, whatever the step size.
+    pub fn exchange(&mut self, ambient_c: f64, conductance: f64, dt: f64) {
+        if self.mass_kg <= 0.0 {
+            return;
+        }
+        let q = conductance * (ambient_c - self.temperature_c()) * dt;
+        // Cooling to a transition temperature stops at the plateau's upper end
+        let target = if q >= 0.0 {
+            self.model.enthalpy_at(ambient_c)
+        } else {
+            self.model.enthalpy_at(ambient_c) + self.model.plateau_at(ambient_c)
+        };
+        let limit = target - self.specific_enthalpy;
+        let dh = q / self.mass_kg;
+        self.specific_enthalpy += if q >= 0.0 {
+            dh.min(limit.max(0.0))
+        } else {
+            dh.max(limit.min(0.0))
+        };
+    }
+
+    fn region(&self) -> Region {
+        let m = &self.model;
+        let h = self.specific_enthalpy;
+        let fusion = if m.melting_point_c.is_some() {
+            m.latent_fusion
+        } else {
+            0.0
+        };
+        if h < 0.0 {
+            return Region::BelowMelting;
+        }
+        if m.melting_point_c.is_some() && h <= fusion {
+            let fraction = if fusion > 0.0 { h / fusion } else { 1.0 };
+            return Region::Melting(fraction);
+        }
+        let Some(tb) = m.boiling_point_c else {
+            return Region::Liquid;
+        };
+        let boil_start = fusion + m.specific_heat_liquid * (tb - m.reference_c());
+        if h <= boil_start {
+            Region::Liquid
+        } else if h <= boil_start + m.latent_vaporization {
+            Region::Boiling((h - boil_start) / m.latent_vaporization)
+        } else {
+            Region::Gas
+        }
+    }
+
+    /// Current temperature in °C
+    pub fn temperature_c(&self) -> f64 {
+        let m = &self.model;
+        let h = self.specific_enthalpy;
+        let reference = m.reference_c();
+        match self.region() {
+            Region::BelowMelting => reference + h / m.low_specific_heat(),
+            Region::Melting(_) => reference,
+            Region::Liquid => {
+                let fusion = if m.melting_point_c.is_some() {
+                    m.latent_fusion
+                } else {
+                    0.0
+                };
+                reference + (h - fusion) / m.specific_heat_liquid
+            }
+            Region::Boiling(_) => m.boiling_point_c.unwrap_or(f64::NAN),
+            Region::Gas => {
+                let tb = m.boiling_point_c.unwrap_or(f64::NAN);
+                tb + (h - m.enthalpy_at(tb) - m.latent_vaporization) / m.specific_heat_gas
+            }
+        }
+    }
+
+    /// Mass share that is liquid (0.0–1.0)
+    pub fn liquid_fraction(&self) -> f64 {
+        match self.region() {
+            Region::BelowMelting if self.model.melting_point_c.is_some() => 0.0,
+            Region::BelowMelting => 1.0,
+            Region::Melting(f) => f,
+            Region::Liquid => 1.0,
+            Region::Boiling(v) => 1.0 - v,
+            Region::Gas => 0.0,
+        }
+    }
+
+    /// Mass share that has vaporized (0.0–1.0)
+    pub fn vapor_fraction(&self) -> f64 {
+        match self.region() {
+            Region::Boiling(v) => v,
+            Region::Gas => 1.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Dominant phase, for conditions like `WaitUntil(water.state.phase == Phase.Liquid)`
+    pub fn phase(&self) -> Phase {
+        match self.region() {
+            Region::BelowMelting if self.model.melting_point_c.is_some() => Phase::Solid,
+            Region::Melting(f) if f < 0.5 => Phase::Solid,
+            Region::Boiling(v) if v >= 0.5 => Phase::Gas,
+            Region::Gas => Phase::Gas,
+            _ => {
+                if self.model.melting_point_c.is_some() {
+                    Phase::Liquid
+                } else {
+                    self.model.base_phase.clone()
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn butter() -> PhaseModel {
+        PhaseModel {
+            melting_point_c: Some(34.0),
+            boiling_point_c: None,
+            latent_fusion: LATENT_FUSION_FAT * 0.81,
+            latent_vaporization: 0.0,
+            specific_heat_solid: 2_000.0,
+            specific_heat_liquid: 2_100.0,
+            specific_heat_gas: 0.0,
+            base_phase: Phase::Solid,
+        }
+    }
+
+    #[test]
+    fn test_ice_melts_with_latent_heat() {
+        let mut ice = PhaseState::new(PhaseModel::water(), 1.0, -10.0);
+        assert_eq!(ice.phase(), Phase::Solid);
+
+        // Warming to 0C takes 21 kJ; melting takes a further 334 kJ
+        ice.add_heat(21_000.0);
+        assert!(ice.temperature_c().abs() < 1e-9);
+        ice.add_heat(100_000.0);
+        assert!(ice.temperature_c().abs() < 1e-9);
+        assert_eq!(ice.phase(), Phase::Solid);
+        ice.add_heat(200_000.0);
+        assert_eq!(ice.phase(), Phase::Liquid);
+        assert!(ice.temperature_c().abs() < 1e-9);
+        ice.add_heat(34_000.0 + 41_860.0);
+        assert!((ice.temperature_c() - 10.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_boiling_plateau() {
+        let mut water = PhaseState::new(PhaseModel::water(), 2.0, 20.0);
+        water.add_heat(2.0 * 4_186.0 * 80.0);
+        assert!((water.temperature_c() - 100.0).abs() < 1e-6);
+        assert_eq!(water.phase(), Phase::Liquid);
+
+        water.add_heat(1_000_000.0);
+        assert!((water.temperature_c() - 100.0).abs() < 1e-6);
+        assert!(water.vapor_fraction() > 0.2 && water.vapor_fraction() < 0.25);
+        assert_eq!(water.phase(), Phase::Liquid);
+
+        water.add_heat(10_000_000.0);
+        assert_eq!(water.phase(), Phase::Gas);
+        assert!(water.temperature_c() > 100.0);
+    }
+
+    #[test]
+    fn test_water_boils_lower_at_altitude() {
+        let model = PhaseModel::water_at_pressure(70.0);
+        let mut water = PhaseState::new(model, 1.0, 20.0);
+        water.add_heat(4_186.0 * 80.0);
+        assert!(water.vapor_fraction() > 0.0);
+        assert!(water.temperature_c() < 95.0);
+    }
+
+    #[test]
+    fn test_freezing_releases_heat() {
+        let mut water = PhaseState::new(PhaseModel::water(), 0.5, 5.0);
+        water.add_heat(-0.5 * 4_186.0 * 5.0 - 0.5 * LATENT_FUSION_WATER);
+        assert_eq!(water.phase(), Phase::Solid);
+        assert!(water.liquid_fraction().abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_butter_melts_in_warm_pan() {
+        let mut butter = PhaseState::new(butter(), 0.05, 5.0);
+        assert_eq!(butter.phase(), Phase::Solid);
+        for _ in 0..600 {
+            butter.exchange(80.0, 2.0, 1.0);
+        }
+        assert_eq!(butter.phase(), Phase::Liquid);
+        assert!(butter.temperature_c() <= 80.0);
+    }
+
+    #[test]
+    fn test_exchange_never_overshoots() {
+        let mut water = PhaseState::new(PhaseModel::water(), 0.1, 20.0);
+        water.exchange(60.0, 1_000.0, 3_600.0);
+        assert!((water.temperature_c() - 60.0).abs() < 1e-9);
+        let mut frozen = PhaseState::new(PhaseModel::water(), 0.1, 20.0);
+        frozen.exchange(-18.0, 1_000.0, 3_600.0);
+        assert!((frozen.temperature_c() + 18.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_exchange_stops_at_plateau_ends() {
+        let model = PhaseModel::water();
+        assert_eq!(model.enthalpy_at(0.0), 0.0);
+        let mut ice = PhaseState::new(model.clone(), 0.1, -10.0);
+        ice.exchange(0.0, 1_000.0, 3_600.0);
+        assert_eq!(ice.phase(), Phase::Solid);
+        let mut water = PhaseState::new(model, 0.1, 10.0);
+        water.exchange(0.0, 1_000.0, 3_600.0);
+        assert_eq!(water.phase(), Phase::Liquid);
+        assert!(water.temperature_c().abs() < 1e-9);
+
+        let mut empty = PhaseState::new(butter(), 0.0, 20.0);
+        empty.exchange(80.0, 2.0, 1.0);
+        assert!(empty.temperature_c().is_finite());
+    }
+
+    #[test]
+    fn test_no_melting_point_keeps_base_phase() {
+        let flour = PhaseModel {
+            melting_point_c: None,
+            boiling_point_c: None,
+            base_phase: Phase::Solid,
+            ..butter()
+        };
+        let state = PhaseState::new(flour, 0.2, 150.0);
+        assert_eq!(state.phase(), Phase::Solid);
+        assert!((state.temperature_c() - 150.0).abs() < 1e-9);
+    }
+}
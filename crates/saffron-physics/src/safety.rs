@@ -0,0 +1,277 @@
+//! # Microbial Safety
+//!
+//! First-order thermal death kinetics. At a constant temperature a pathogen
+//! population falls by one log₁₀ every `D(T)` seconds, and `D` itself falls
+//! tenfold for every `z` degrees of heating:
+//!
+//! ```text
+//! D(T) = D_ref · 10^((T_ref − T) / z)
+//! log reduction = ∫ dt / D(T(t))
+//! ```
+//!
+//! Integrating over a core temperature history shows whether a gentle
+//! `SousVide` or `Poach` pasteurizes as well as a quick high-temperature cook.
+//! The model covers killing only; time spent warm but below lethal
+//! temperatures is reported separately as time in the danger zone.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Lower bound of the bacterial growth range, °C
+const DANGER_ZONE_LOW_C: f64 = 5.0;
+
+/// Upper bound of the growth range; pathogens stop multiplying above this, °C
+const DANGER_ZONE_HIGH_C: f64 = 52.0;
+
+/// D/z parameters for one organism in one food
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ThermalDeathKinetics {
+    /// Decimal reduction time at `reference_temp_c`, s
+    pub d_ref_s: f64,
+    pub reference_temp_c: f64,
+    /// Temperature rise that cuts `D` tenfold, °C
+    pub z_c: f64,
+}
+
+impl ThermalDeathKinetics {
+    /// Decimal reduction time at `temperature_c`, s
+    pub fn d_value(&self, temperature_c: f64) -> f64 {
+        self.d_ref_s * 10f64.powf((self.reference_temp_c - temperature_c) / self.z_c)
+    }
+
+    /// Log₁₀ reductions per second at `temperature_c`
+    pub fn rate(&self, temperature_c: f64) -> f64 {
+        1.0 / self.d_value(temperature_c)
+    }
+
+    /// Log₁₀ reduction over `(time_s, temperature_c)` samples (trapezoidal)
+    pub fn log_reduction(&self, history: &[(f64, f64)]) -> f64 {
+        history
+            .windows(2)
+            .map(|pair| {
+                let (t0, temp0) = pair[0];
+                let (t1, temp1) = pair[1];
+                (self.rate(temp0) + self.rate(temp1)) / 2.0 * (t1 - t0)
+            })
+            .sum()
+    }
+
+    /// Seconds at a constant `temperature_c` to reach `target_log` reductions
+    pub fn hold_time_s(&self, temperature_c: f64, target_log: f64) -> f64 {
+        target_log * self.d_value(temperature_c)
+    }
+}
+
+/// Pathogens with tabulated thermal death kinetics (values for meat and poultry)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Pathogen {
+    Salmonella,
+    Listeria,
+    EColi,
+}
+
+impl Pathogen {
+    pub const ALL: [Pathogen; 3] = [Pathogen::Salmonella, Pathogen::Listeria, Pathogen::EColi];
+
+    pub fn kinetics(self) -> ThermalDeathKinetics {
+        let (d_ref_s, z_c) = match self {
+            // Consistent with the USDA-FSIS poultry time/temperature tables
+            Pathogen::Salmonella => (230.0, 5.6),
+            Pathogen::Listeria => (300.0, 6.0),
+            Pathogen::EColi => (45.0, 5.3),
+        };
+        ThermalDeathKinetics {
+            d_ref_s,
+            reference_temp_c: 60.0,
+            z_c,
+        }
+    }
+
+    /// Log₁₀ reduction conventionally required for pasteurization
+    pub fn default_target_log(self) -> f64 {
+        match self {
+            Pathogen::Salmonella => 7.0,
+            Pathogen::Listeria => 6.0,
+            Pathogen::EColi => 5.0,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Pathogen::Salmonella => "Salmonella",
+            Pathogen::Listeria => "Listeria monocytogenes",
+            Pathogen::EColi => "E. coli O157:H7",
+        }
+    }
+}
+
+/// Achieved vs. required reduction for one pathogen
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PathogenResult {
+    pub pathogen: Pathogen,
+    pub achieved_log: f64,
+    pub target_log: f64,
+}
+
+impl PathogenResult {
+    pub fn is_safe(&self) -> bool {
+        self.achieved_log >= self.target_log
+    }
+}
+
+/// Pasteurization report for a core temperature history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SafetyReport {
+    pub results: Vec<PathogenResult>,
+    pub peak_core_c: f64,
+    /// Time the core spent between 5 and 52°C, where pathogens can grow, s
+    pub danger_zone_s: f64,
+}
+
+impl SafetyReport {
+    /// Assess `(time_s, core_temp_c)` samples against explicit `(pathogen, target_log)` pairs
+    pub fn assess(history: &[(f64, f64)], targets: &[(Pathogen, f64)]) -> Self {
+        let results = targets
+            .iter()
+            .map(|&(pathogen, target_log)| PathogenResult {
+                pathogen,
+                achieved_log: pathogen.kinetics().log_reduction(history),
+                target_log,
+            })
+            .collect();
+        let peak_core_c = history
+            .iter()
+            .map(|&(_, temp)| temp)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let danger_zone_s = history
+            .windows(2)
+            .filter(|pair| {
+                let mid = (pair[0].1 + pair[1].1) / 2.0;
+                (DANGER_ZONE_LOW_C..DANGER_ZONE_HIGH_C).contains(&mid)
+            })
+            .map(|pair| pair[1].0 - pair[0].0)
+            .sum();
+        Self {
+            results,
+            peak_core_c,
+            danger_zone_s,
+        }
+    }
+
+    /// Assess against every pathogen at its default target
+    pub fn assess_default(history: &[(f64, f64)]) -> Self {
+        let targets: Vec<_> = Pathogen::ALL
+            .iter()
+            .map(|&p| (p, p.default_target_log()))
+            .collect();
+        Self::assess(history, &targets)
+    }
+
+    pub fn is_safe(&self) -> bool {
+        self.results.iter().all(PathogenResult::is_safe)
+    }
+
+    pub fn result(&self, pathogen: Pathogen) -> Option<&PathogenResult> {
+        self.results.iter().find(|r| r.pathogen == pathogen)
+    }
+}
+
+impl fmt::Display for SafetyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Peak core temperature: {:.1}°C", self.peak_core_c)?;
+        writeln!(
+            f,
+            "Time in danger zone: {:.0} min",
+            self.danger_zone_s / 60.0
+        )?;
+        for r in &self.results {
+            writeln!(
+                f,
+                "{:<24} {:>6.1} log (target {:.1}) {}",
+                r.pathogen.name(),
+                r.achieved_log,
+                r.target_log,
+                if r.is_safe() { "OK" } else { "UNSAFE" }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conduction::{BoundaryCondition, ConductionSolver, Geometry, ThermalProperties};
+
+    fn chicken_props() -> ThermalProperties {
+        ThermalProperties {
+            conductivity: 0.48,
+            density: 1050.0,
+            specific_heat: 3500.0,
+        }
+    }
+
+    /// Core temperature of a 2.5 cm chicken breast in a water bath, sampled every 30 s
+    fn water_bath(bath_c: f64, duration_s: f64) -> Vec<(f64, f64)> {
+        let bath = BoundaryCondition::Convective {
+            ambient_c: bath_c,
+            h: 500.0,
+        };
+        let mut solver = ConductionSolver::new(Geometry::Slab, 0.025, chicken_props(), 21, 5.0)
+            .with_boundaries(bath, bath);
+        // The first sample is the initial (0.0, 5.0) state
+        solver
+            .simulate(duration_s, 30.0)
+            .unwrap()
+            .iter()
+            .map(|p| (p.time_s, p.core_c()))
+            .collect()
+    }
+
+    #[test]
+    fn test_z_value_means_tenfold() {
+        let k = Pathogen::Salmonella.kinetics();
+        assert!((k.d_value(60.0) / k.d_value(65.6) - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fsis_poultry_hold_times() {
+        let k = Pathogen::Salmonella.kinetics();
+        let at_60 = k.hold_time_s(60.0, 7.0) / 60.0;
+        let at_74 = k.hold_time_s(73.9, 7.0);
+        assert!(at_60 > 20.0 && at_60 < 35.0, "{at_60} min at 60°C");
+        assert!(at_74 < 10.0, "{at_74} s at 74°C");
+    }
+
+    #[test]
+    fn test_constant_history_matches_closed_form() {
+        let k = Pathogen::Listeria.kinetics();
+        let history = [(0.0, 62.0), (600.0, 62.0)];
+        assert!((k.log_reduction(&history) - 600.0 / k.d_value(62.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sous_vide_chicken_at_60_is_safe() {
+        let history = water_bath(60.0, 2.5 * 3600.0);
+        let report = SafetyReport::assess_default(&history);
+        assert!(report.is_safe(), "{report}");
+        assert!(report.peak_core_c > 59.0);
+    }
+
+    #[test]
+    fn test_sous_vide_at_55_for_an_hour_is_not() {
+        let history = water_bath(55.0, 3600.0);
+        let report = SafetyReport::assess_default(&history);
+        assert!(!report.is_safe());
+        let salmonella = report.result(Pathogen::Salmonella).unwrap();
+        assert!(salmonella.achieved_log < salmonella.target_log);
+        assert!(report.to_string().contains("UNSAFE"));
+    }
+
+    #[test]
+    fn test_danger_zone_time() {
+        let history = [(0.0, 20.0), (3600.0, 20.0), (3660.0, 90.0)];
+        let report = SafetyReport::assess_default(&history);
+        assert!((report.danger_zone_s - 3600.0).abs() < 1e-9);
+    }
+}
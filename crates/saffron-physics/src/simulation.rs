@@ -0,0 +1,458 @@
+//! # Simulation Configuration
+//!
+//! Seeded stochastic variation for reproducible runs. Natural variability
+//! (egg size, fridge temperature, burner output) is described by named
+//! parameter [`Distribution`]s; every run draws its values from a
+//! [`SplitMix64`] stream derived from the configuration seed, the run index
+//! and the parameter name, so a run is reproducible on its own and adding a
+//! parameter does not shift the values drawn for the others.
+//!
+//! [`MonteCarlo`] repeats a simulation over many runs and reports how often
+//! each expected property was hit.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use thiserror::Error;
+
+/// SplitMix64 pseudo-random generator (Steele, Lea & Flood, 2014)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Standard normal (Box–Muller)
+    pub fn next_normal(&mut self) -> f64 {
+        let u1 = 1.0 - self.next_f64();
+        let u2 = self.next_f64();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+}
+
+/// Distribution of a varied parameter
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Distribution {
+    Fixed(f64),
+    Uniform { min: f64, max: f64 },
+    Normal { mean: f64, std_dev: f64 },
+    Triangular { min: f64, mode: f64, max: f64 },
+}
+
+/// Why a distribution cannot be sampled
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum DistributionError {
+    #[error("Distribution parameters must be finite")]
+    NonFinite,
+
+    #[error("Minimum {min} is above maximum {max}")]
+    InvertedRange { min: f64, max: f64 },
+
+    #[error("Standard deviation {std_dev} is negative")]
+    NegativeStdDev { std_dev: f64 },
+
+    #[error("Mode {mode} lies outside [{min}, {max}]")]
+    ModeOutOfRange { min: f64, mode: f64, max: f64 },
+}
+
+/// A configured parameter with an invalid distribution
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("Parameter `{parameter}`: {error}")]
+pub struct InvalidParameter {
+    pub parameter: String,
+    pub error: DistributionError,
+}
+
+impl Distribution {
+    /// Check the bounds are finite and ordered and the spread is non-negative
+    pub fn validate(&self) -> Result<(), DistributionError> {
+        let values: &[f64] = match self {
+            Distribution::Fixed(value) => &[*value],
+            Distribution::Uniform { min, max } => &[*min, *max],
+            Distribution::Normal { mean, std_dev } => &[*mean, *std_dev],
+            Distribution::Triangular { min, mode, max } => &[*min, *mode, *max],
+        };
+        if !values.iter().all(|v| v.is_finite()) {
+            return Err(DistributionError::NonFinite);
+        }
+        match *self {
+            Distribution::Uniform { min, max } | Distribution::Triangular { min, max, .. }
+                if min > max =>
+            {
+                Err(DistributionError::InvertedRange { min, max })
+            }
+            Distribution::Normal { std_dev, .. } if std_dev < 0.0 => {
+                Err(DistributionError::NegativeStdDev { std_dev })
+            }
+            Distribution::Triangular { min, mode, max } if mode < min || mode > max => {
+                Err(DistributionError::ModeOutOfRange { min, mode, max })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Draw one value; a range with `max <= min` always yields `min`
+    pub fn sample(&self, rng: &mut SplitMix64) -> f64 {
+        match *self {
+            Distribution::Fixed(value) => value,
+            Distribution::Uniform { min, max } | Distribution::Triangular { min, max, .. }
+                if max <= min =>
+            {
+                min
+            }
+            Distribution::Uniform { min, max } => min + (max - min) * rng.next_f64(),
+            Distribution::Normal { mean, std_dev } => mean + std_dev * rng.next_normal(),
+            Distribution::Triangular { min, mode, max } => {
+                let u = rng.next_f64();
+                let mode = mode.clamp(min, max);
+                let split = (mode - min) / (max - min);
+                if u < split {
+                    min + (u * (max - min) * (mode - min)).sqrt()
+                } else {
+                    max - ((1.0 - u) * (max - min) * (max - mode)).sqrt()
+                }
+            }
+        }
+    }
+
+    pub fn mean(&self) -> f64 {
+        match *self {
+            Distribution::Fixed(value) => value,
+            Distribution::Uniform { min, max } => (min + max) / 2.0,
+            Distribution::Normal { mean, .. } => mean,
+            Distribution::Triangular { min, mode, max } => (min + mode + max) / 3.0,
+        }
+    }
+}
+
+/// Values drawn for one run
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParameterSet {
+    pub run: u64,
+    pub values: BTreeMap<String, f64>,
+}
+
+impl ParameterSet {
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.values.get(name).copied()
+    }
+
+    pub fn get_or(&self, name: &str, default: f64) -> f64 {
+        self.get(name).unwrap_or(default)
+    }
+}
+
+/// Seed and parameter distributions for a simulation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimulationConfig {
+    pub seed: u64,
+    pub parameters: BTreeMap<String, Distribution>,
+    /// Relative tolerance when comparing numeric results with expected values
+    pub tolerance: f64,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl SimulationConfig {
+    /// Deterministic configuration: no varied parameters
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            parameters: BTreeMap::new(),
+            tolerance: 0.05,
+        }
+    }
+
+    /// Typical home-kitchen variability
+    pub fn kitchen(seed: u64) -> Self {
+        Self::new(seed)
+            // Large eggs: 50–63 g
+            .with_parameter(
+                "egg_mass_g",
+                Distribution::Normal {
+                    mean: 57.0,
+                    std_dev: 3.5,
+                },
+            )
+            .with_parameter(
+                "fridge_temp_c",
+                Distribution::Triangular {
+                    min: 1.0,
+                    mode: 4.0,
+                    max: 8.0,
+                },
+            )
+            .with_parameter(
+                "burner_power_factor",
+                Distribution::Normal {
+                    mean: 1.0,
+                    std_dev: 0.05,
+                },
+            )
+            .with_parameter(
+                "kitchen_air_c",
+                Distribution::Uniform {
+                    min: 18.0,
+                    max: 26.0,
+                },
+            )
+    }
+
+    pub fn with_parameter(mut self, name: impl Into<String>, distribution: Distribution) -> Self {
+        self.parameters.insert(name.into(), distribution);
+        self
+    }
+
+    /// Check every parameter distribution, see [`Distribution::validate`]
+    pub fn validate(&self) -> Result<(), InvalidParameter> {
+        for (name, distribution) in &self.parameters {
+            distribution.validate().map_err(|error| InvalidParameter {
+                parameter: name.clone(),
+                error,
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Draw the parameter values for run `run`
+    pub fn sample(&self, run: u64) -> ParameterSet {
+        let run_seed = SplitMix64::new(self.seed ^ SplitMix64::new(run).next_u64()).next_u64();
+        let values = self
+            .parameters
+            .iter()
+            .map(|(name, distribution)| {
+                let mut rng = SplitMix64::new(run_seed ^ name_hash(name));
+                (name.clone(), distribution.sample(&mut rng))
+            })
+            .collect();
+        ParameterSet { run, values }
+    }
+
+    /// Whether `observed` is within tolerance of `expected`
+    pub fn matches(&self, expected: f64, observed: f64) -> bool {
+        (observed - expected).abs() <= self.tolerance * expected.abs().max(1.0)
+    }
+}
+
+/// FNV-1a, stable across platforms and releases
+fn name_hash(name: &str) -> u64 {
+    name.bytes().fold(0xCBF2_9CE4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01B3)
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Monte Carlo
+// ---------------------------------------------------------------------------
+
+/// Hit counts over a batch of runs
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BatchReport {
+    pub runs: usize,
+    /// Runs in which each property was hit
+    pub hits: BTreeMap<String, usize>,
+    /// Runs in which every property was hit
+    pub all_hit: usize,
+}
+
+impl BatchReport {
+    pub fn probability(&self, property: &str) -> Option<f64> {
+        let hits = *self.hits.get(property)?;
+        Some(hits as f64 / self.runs.max(1) as f64)
+    }
+
+    pub fn overall_probability(&self) -> f64 {
+        self.all_hit as f64 / self.runs.max(1) as f64
+    }
+}
+
+impl fmt::Display for BatchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} runs", self.runs)?;
+        for property in self.hits.keys() {
+            let p = self.probability(property).unwrap_or(0.0);
+            writeln!(f, "{:<24} {:>5.1}%", property, p * 100.0)?;
+        }
+        writeln!(
+            f,
+            "{:<24} {:>5.1}%",
+            "all",
+            self.overall_probability() * 100.0
+        )
+    }
+}
+
+/// Repeats a simulation over sampled parameter sets
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonteCarlo {
+    pub config: SimulationConfig,
+    pub runs: usize,
+}
+
+impl MonteCarlo {
+    pub fn new(config: SimulationConfig, runs: usize) -> Self {
+        Self { config, runs }
+    }
+
+    /// Run `simulate` once per parameter set; it reports whether each named property was hit
+    pub fn run<F>(&self, properties: &[&str], mut simulate: F) -> BatchReport
+    where
+        F: FnMut(&ParameterSet) -> BTreeMap<String, bool>,
+    {
+        let mut report = BatchReport {
+            runs: self.runs,
+            hits: properties.iter().map(|p| (p.to_string(), 0)).collect(),
+            all_hit: 0,
+        };
+        for run in 0..self.runs as u64 {
+            let outcome = simulate(&self.config.sample(run));
+            let mut all = true;
+            for property in properties {
+                if outcome.get(*property).copied().unwrap_or(false) {
+                    *report.hits.entry(property.to_string()).or_default() += 1;
+                } else {
+                    all = false;
+                }
+            }
+            if all {
+                report.all_hit += 1;
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_splitmix_reference_values() {
+        let mut rng = SplitMix64::new(1234567);
+        assert_eq!(rng.next_u64(), 6457827717110365317);
+        assert_eq!(rng.next_u64(), 3203168211198807973);
+    }
+
+    #[test]
+    fn test_sampling_is_reproducible() {
+        let config = SimulationConfig::kitchen(42);
+        assert_eq!(config.sample(7), config.sample(7));
+        assert_ne!(config.sample(7), config.sample(8));
+        assert_ne!(
+            config.sample(7).values,
+            SimulationConfig::kitchen(43).sample(7).values
+        );
+    }
+
+    #[test]
+    fn test_adding_a_parameter_keeps_other_draws() {
+        let base = SimulationConfig::kitchen(42);
+        let extended = base
+            .clone()
+            .with_parameter("salt_g", Distribution::Uniform { min: 1.0, max: 2.0 });
+        let (a, b) = (base.sample(3), extended.sample(3));
+        assert_eq!(a.get("egg_mass_g"), b.get("egg_mass_g"));
+        assert!(b.get("salt_g").is_some());
+    }
+
+    #[test]
+    fn test_distribution_moments() {
+        let mut rng = SplitMix64::new(9);
+        for distribution in [
+            Distribution::Uniform { min: 2.0, max: 6.0 },
+            Distribution::Normal {
+                mean: 57.0,
+                std_dev: 3.5,
+            },
+            Distribution::Triangular {
+                min: 1.0,
+                mode: 4.0,
+                max: 8.0,
+            },
+        ] {
+            let n = 20_000;
+            let mean = (0..n).map(|_| distribution.sample(&mut rng)).sum::<f64>() / n as f64;
+            assert!(
+                (mean - distribution.mean()).abs() < 0.1,
+                "{distribution:?}: {mean}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_degenerate_distributions() {
+        let mut rng = SplitMix64::new(3);
+        let point = Distribution::Triangular {
+            min: 4.0,
+            mode: 4.0,
+            max: 4.0,
+        };
+        assert_eq!(point.sample(&mut rng), 4.0);
+        assert!(point.validate().is_ok());
+        let inverted = Distribution::Uniform { min: 8.0, max: 1.0 };
+        assert_eq!(inverted.sample(&mut rng), 8.0);
+        assert!(matches!(
+            inverted.validate(),
+            Err(DistributionError::InvertedRange { .. })
+        ));
+        let skewed = Distribution::Triangular {
+            min: 1.0,
+            mode: 9.0,
+            max: 8.0,
+        };
+        assert!(skewed.sample(&mut rng).is_finite());
+        assert!(skewed.validate().is_err());
+
+        let config = SimulationConfig::kitchen(1).with_parameter(
+            "oven_offset_c",
+            Distribution::Normal {
+                mean: 0.0,
+                std_dev: -5.0,
+            },
+        );
+        let error = config.validate().unwrap_err();
+        assert_eq!(error.parameter, "oven_offset_c");
+        assert!(SimulationConfig::kitchen(1).validate().is_ok());
+    }
+
+    #[test]
+    fn test_monte_carlo_probability() {
+        let config = SimulationConfig::new(1)
+            .with_parameter("x", Distribution::Uniform { min: 0.0, max: 1.0 });
+        let report = MonteCarlo::new(config.clone(), 2000).run(&["low", "any"], |params| {
+            let x = params.get_or("x", 0.0);
+            BTreeMap::from([("low".to_string(), x < 0.25), ("any".to_string(), true)])
+        });
+        let low = report.probability("low").unwrap();
+        assert!((low - 0.25).abs() < 0.04, "{low}");
+        assert_eq!(report.probability("any"), Some(1.0));
+        assert_eq!(report.all_hit, report.hits["low"]);
+
+        let again = MonteCarlo::new(config, 2000).run(&["low", "any"], |params| {
+            BTreeMap::from([("low".to_string(), params.get_or("x", 0.0) < 0.25)])
+        });
+        assert_eq!(again.hits["low"], report.hits["low"]);
+        assert_eq!(again.probability("any"), Some(0.0));
+    }
+}
@@ -0,0 +1,505 @@
+//! # Physical Quantities
+//!
+//! Strongly typed quantities with conversions between every `saffron_ast::Unit`:
+//!
+//! | Quantity      | Stored as | Units                                          |
+//! |---------------|-----------|------------------------------------------------|
+//! | `Temperature` | °C        | Celsius, Fahrenheit, Kelvin                    |
+//! | `Mass`        | kg        | Milligrams, Grams, Kilograms, Ounces, Pounds   |
+//! | `Volume`      | m³        | Milliliters, Liters, Teaspoons, Tablespoons, FluidOunces, Cups |
+//! | `Duration`    | s         | Seconds, Minutes, Hours                        |
+//! | `Length`      | m         | Millimeters, Centimeters, Inches               |
+//! | `Energy`      | J         | Joules, Calories, Kilocalories                 |
+//! | `Power`       | W         | Watts                                          |
+//!
+//! Kitchen volumes are US customary (1 cup = 236.59 ml, 1 tbsp = 3 tsp =
+//! 14.79 ml); ounces and pounds are avoirdupois.
+
+use saffron_ast::{Expr, Param, Unit};
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, Div, Mul, Sub};
+use thiserror::Error;
+
+/// What a unit measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Dimension {
+    Temperature,
+    Mass,
+    Volume,
+    Time,
+    Length,
+    Energy,
+    Power,
+    /// Dimensionless (percentages)
+    Ratio,
+}
+
+impl Dimension {
+    pub fn of(unit: &Unit) -> Self {
+        match unit {
+            Unit::Celsius | Unit::Fahrenheit | Unit::Kelvin => Dimension::Temperature,
+            Unit::Grams | Unit::Kilograms | Unit::Ounces | Unit::Pounds | Unit::Milligrams => {
+                Dimension::Mass
+            }
+            Unit::Milliliters
+            | Unit::Liters
+            | Unit::Cups
+            | Unit::Tablespoons
+            | Unit::Teaspoons
+            | Unit::FluidOunces => Dimension::Volume,
+            Unit::Seconds | Unit::Minutes | Unit::Hours => Dimension::Time,
+            Unit::Centimeters | Unit::Millimeters | Unit::Inches => Dimension::Length,
+            Unit::Joules | Unit::Calories | Unit::Kilocalories => Dimension::Energy,
+            Unit::Watts => Dimension::Power,
+            Unit::Percent => Dimension::Ratio,
+        }
+    }
+}
+
+/// Unit conversion errors
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum UnitError {
+    #[error("Cannot convert {from:?} to {to:?}: {from_dim:?} is not {to_dim:?}")]
+    Incompatible {
+        from: Unit,
+        to: Unit,
+        from_dim: Dimension,
+        to_dim: Dimension,
+    },
+
+    #[error("{unit:?} is not a unit of {expected:?}")]
+    WrongDimension { unit: Unit, expected: Dimension },
+}
+
+/// Factor from `unit` to the SI base of its dimension (`None` for temperatures, which are affine)
+fn si_factor(unit: &Unit) -> Option<f64> {
+    const TEASPOON_M3: f64 = 4.928_921_593_75e-6;
+    let factor = match unit {
+        Unit::Celsius | Unit::Fahrenheit | Unit::Kelvin => return None,
+        Unit::Milligrams => 1e-6,
+        Unit::Grams => 1e-3,
+        Unit::Kilograms => 1.0,
+        Unit::Ounces => 0.028_349_523_125,
+        Unit::Pounds => 0.453_592_37,
+        Unit::Milliliters => 1e-6,
+        Unit::Liters => 1e-3,
+        Unit::Teaspoons => TEASPOON_M3,
+        Unit::Tablespoons => 3.0 * TEASPOON_M3,
+        Unit::FluidOunces => 6.0 * TEASPOON_M3,
+        Unit::Cups => 48.0 * TEASPOON_M3,
+        Unit::Seconds => 1.0,
+        Unit::Minutes => 60.0,
+        Unit::Hours => 3600.0,
+        Unit::Millimeters => 1e-3,
+        Unit::Centimeters => 1e-2,
+        Unit::Inches => 0.0254,
+        Unit::Joules => 1.0,
+        Unit::Calories => 4.184,
+        Unit::Kilocalories => 4184.0,
+        Unit::Watts => 1.0,
+        Unit::Percent => 0.01,
+    };
+    Some(factor)
+}
+
+/// Convert `value` from one unit to another of the same dimension
+pub fn convert(value: f64, from: &Unit, to: &Unit) -> Result<f64, UnitError> {
+    let (from_dim, to_dim) = (Dimension::of(from), Dimension::of(to));
+    if from_dim != to_dim {
+        return Err(UnitError::Incompatible {
+            from: from.clone(),
+            to: to.clone(),
+            from_dim,
+            to_dim,
+        });
+    }
+    match (si_factor(from), si_factor(to)) {
+        (Some(a), Some(b)) => Ok(value * a / b),
+        _ => Ok(Temperature::new(value, from)?.value_in(to)?),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Temperature
+// ---------------------------------------------------------------------------
+
+/// An absolute temperature
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Temperature {
+    celsius: f64,
+}
+
+impl Temperature {
+    pub fn new(value: f64, unit: &Unit) -> Result<Self, UnitError> {
+        match unit {
+            Unit::Celsius => Ok(Self::from_celsius(value)),
+            Unit::Fahrenheit => Ok(Self::from_fahrenheit(value)),
+            Unit::Kelvin => Ok(Self::from_kelvin(value)),
+            other => Err(UnitError::WrongDimension {
+                unit: other.clone(),
+                expected: Dimension::Temperature,
+            }),
+        }
+    }
+
+    pub fn from_celsius(celsius: f64) -> Self {
+        Self { celsius }
+    }
+
+    pub fn from_fahrenheit(fahrenheit: f64) -> Self {
+        Self::from_celsius((fahrenheit - 32.0) * 5.0 / 9.0)
+    }
+
+    pub fn from_kelvin(kelvin: f64) -> Self {
+        Self::from_celsius(kelvin - 273.15)
+    }
+
+    pub fn celsius(self) -> f64 {
+        self.celsius
+    }
+
+    pub fn fahrenheit(self) -> f64 {
+        self.celsius * 9.0 / 5.0 + 32.0
+    }
+
+    pub fn kelvin(self) -> f64 {
+        self.celsius + 273.15
+    }
+
+    pub fn value_in(self, unit: &Unit) -> Result<f64, UnitError> {
+        match unit {
+            Unit::Celsius => Ok(self.celsius()),
+            Unit::Fahrenheit => Ok(self.fahrenheit()),
+            Unit::Kelvin => Ok(self.kelvin()),
+            other => Err(UnitError::WrongDimension {
+                unit: other.clone(),
+                expected: Dimension::Temperature,
+            }),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Linear quantities
+// ---------------------------------------------------------------------------
+
+macro_rules! linear_quantity {
+    ($(#[$doc:meta])* $name:ident, $dimension:expr, $si:ident) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default, Serialize, Deserialize)]
+        pub struct $name {
+            $si: f64,
+        }
+
+        impl $name {
+            pub fn new(value: f64, unit: &Unit) -> Result<Self, UnitError> {
+                Self::factor(unit).map(|f| Self { $si: value * f })
+            }
+
+            /// Construct from a value in SI base units
+            pub fn $si(value: f64) -> Self {
+                Self { $si: value }
+            }
+
+            /// Value in SI base units
+            pub fn si(self) -> f64 {
+                self.$si
+            }
+
+            pub fn value_in(self, unit: &Unit) -> Result<f64, UnitError> {
+                Self::factor(unit).map(|f| self.$si / f)
+            }
+
+            fn factor(unit: &Unit) -> Result<f64, UnitError> {
+                match si_factor(unit) {
+                    Some(f) if Dimension::of(unit) == $dimension => Ok(f),
+                    _ => Err(UnitError::WrongDimension {
+                        unit: unit.clone(),
+                        expected: $dimension,
+                    }),
+                }
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                Self { $si: self.$si + rhs.$si }
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                Self { $si: self.$si - rhs.$si }
+            }
+        }
+
+        impl Mul<f64> for $name {
+            type Output = Self;
+            fn mul(self, rhs: f64) -> Self {
+                Self { $si: self.$si * rhs }
+            }
+        }
+
+        impl Div<f64> for $name {
+            type Output = Self;
+            fn div(self, rhs: f64) -> Self {
+                Self { $si: self.$si / rhs }
+            }
+        }
+    };
+}
+
+linear_quantity!(
+    /// A mass
+    Mass,
+    Dimension::Mass,
+    kilograms
+);
+linear_quantity!(
+    /// A volume
+    Volume,
+    Dimension::Volume,
+    cubic_meters
+);
+linear_quantity!(
+    /// A span of time
+    Duration,
+    Dimension::Time,
+    seconds
+);
+linear_quantity!(
+    /// A length
+    Length,
+    Dimension::Length,
+    meters
+);
+linear_quantity!(
+    /// An amount of energy
+    Energy,
+    Dimension::Energy,
+    joules
+);
+linear_quantity!(
+    /// A rate of energy transfer
+    Power,
+    Dimension::Power,
+    watts
+);
+
+impl Volume {
+    pub fn liters(self) -> f64 {
+        self.cubic_meters * 1000.0
+    }
+}
+
+impl Power {
+    /// Energy delivered over `duration`
+    pub fn over(self, duration: Duration) -> Energy {
+        Energy::joules(self.watts * duration.si())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Dynamically typed quantities
+// ---------------------------------------------------------------------------
+
+/// A quantity whose dimension is only known at run time, e.g. from a unit literal
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Quantity {
+    Temperature(Temperature),
+    Mass(Mass),
+    Volume(Volume),
+    Duration(Duration),
+    Length(Length),
+    Energy(Energy),
+    Power(Power),
+    /// Fraction (76% → 0.76)
+    Ratio(f64),
+}
+
+impl Quantity {
+    pub fn new(value: f64, unit: &Unit) -> Self {
+        // Every unit belongs to exactly one dimension, so these cannot fail
+        let quantity = match Dimension::of(unit) {
+            Dimension::Temperature => Temperature::new(value, unit).map(Quantity::Temperature),
+            Dimension::Mass => Mass::new(value, unit).map(Quantity::Mass),
+            Dimension::Volume => Volume::new(value, unit).map(Quantity::Volume),
+            Dimension::Time => Duration::new(value, unit).map(Quantity::Duration),
+            Dimension::Length => Length::new(value, unit).map(Quantity::Length),
+            Dimension::Energy => Energy::new(value, unit).map(Quantity::Energy),
+            Dimension::Power => Power::new(value, unit).map(Quantity::Power),
+            Dimension::Ratio => Ok(Quantity::Ratio(value / 100.0)),
+        };
+        quantity.unwrap_or(Quantity::Ratio(value))
+    }
+
+    /// Read a `UnitLiteral` or `PercentLiteral` expression
+    pub fn from_expr(expr: &Expr) -> Option<Self> {
+        match expr {
+            Expr::UnitLiteral { value, unit, .. } => Some(Self::new(*value, unit)),
+            Expr::PercentLiteral { value, .. } => Some(Quantity::Ratio(value / 100.0)),
+            _ => None,
+        }
+    }
+
+    /// Read the named parameter of a declaration or process call
+    pub fn from_param(params: &[Param], name: &str) -> Option<Self> {
+        params
+            .iter()
+            .find(|p| p.name == name)
+            .and_then(|p| Self::from_expr(&p.value))
+    }
+
+    pub fn dimension(&self) -> Dimension {
+        match self {
+            Quantity::Temperature(_) => Dimension::Temperature,
+            Quantity::Mass(_) => Dimension::Mass,
+            Quantity::Volume(_) => Dimension::Volume,
+            Quantity::Duration(_) => Dimension::Time,
+            Quantity::Length(_) => Dimension::Length,
+            Quantity::Energy(_) => Dimension::Energy,
+            Quantity::Power(_) => Dimension::Power,
+            Quantity::Ratio(_) => Dimension::Ratio,
+        }
+    }
+
+    /// Value in `unit`, which must share this quantity's dimension
+    pub fn value_in(&self, unit: &Unit) -> Result<f64, UnitError> {
+        match self {
+            Quantity::Temperature(t) => t.value_in(unit),
+            Quantity::Mass(q) => q.value_in(unit),
+            Quantity::Volume(q) => q.value_in(unit),
+            Quantity::Duration(q) => q.value_in(unit),
+            Quantity::Length(q) => q.value_in(unit),
+            Quantity::Energy(q) => q.value_in(unit),
+            Quantity::Power(q) => q.value_in(unit),
+            Quantity::Ratio(r) => match unit {
+                Unit::Percent => Ok(r * 100.0),
+                other => Err(UnitError::WrongDimension {
+                    unit: other.clone(),
+                    expected: Dimension::Ratio,
+                }),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9 * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn test_temperature_conversions() {
+        assert!(close(
+            convert(180.0, &Unit::Celsius, &Unit::Fahrenheit).unwrap(),
+            356.0
+        ));
+        assert!(close(
+            convert(212.0, &Unit::Fahrenheit, &Unit::Kelvin).unwrap(),
+            373.15
+        ));
+        assert!(close(Temperature::from_kelvin(0.0).fahrenheit(), -459.67));
+    }
+
+    #[test]
+    fn test_kitchen_volumes() {
+        assert!(close(
+            convert(1.0, &Unit::Tablespoons, &Unit::Teaspoons).unwrap(),
+            3.0
+        ));
+        assert!(close(
+            convert(1.0, &Unit::Cups, &Unit::FluidOunces).unwrap(),
+            8.0
+        ));
+        assert!((convert(1.0, &Unit::Cups, &Unit::Milliliters).unwrap() - 236.588).abs() < 1e-3);
+        assert!(close(
+            Volume::new(2.5, &Unit::Liters).unwrap().liters(),
+            2.5
+        ));
+    }
+
+    #[test]
+    fn test_mass_and_energy() {
+        assert!(close(
+            convert(1.0, &Unit::Pounds, &Unit::Ounces).unwrap(),
+            16.0
+        ));
+        assert!(close(
+            convert(500.0, &Unit::Grams, &Unit::Kilograms).unwrap(),
+            0.5
+        ));
+        assert!(close(
+            convert(1.0, &Unit::Kilocalories, &Unit::Joules).unwrap(),
+            4184.0
+        ));
+        let energy = Power::watts(2000.0).over(Duration::new(2.0, &Unit::Minutes).unwrap());
+        assert!(close(energy.si(), 240_000.0));
+    }
+
+    #[test]
+    fn test_round_trip_every_unit() {
+        let units = [
+            Unit::Celsius,
+            Unit::Fahrenheit,
+            Unit::Kelvin,
+            Unit::Grams,
+            Unit::Kilograms,
+            Unit::Ounces,
+            Unit::Pounds,
+            Unit::Milligrams,
+            Unit::Milliliters,
+            Unit::Liters,
+            Unit::Cups,
+            Unit::Tablespoons,
+            Unit::Teaspoons,
+            Unit::FluidOunces,
+            Unit::Seconds,
+            Unit::Minutes,
+            Unit::Hours,
+            Unit::Centimeters,
+            Unit::Millimeters,
+            Unit::Inches,
+            Unit::Joules,
+            Unit::Calories,
+            Unit::Kilocalories,
+            Unit::Watts,
+            Unit::Percent,
+        ];
+        for from in &units {
+            for to in units
+                .iter()
+                .filter(|u| Dimension::of(u) == Dimension::of(from))
+            {
+                let there = convert(42.0, from, to).unwrap();
+                assert!(
+                    close(convert(there, to, from).unwrap(), 42.0),
+                    "{from:?} <-> {to:?}"
+                );
+                assert!(close(
+                    Quantity::new(42.0, from).value_in(to).unwrap(),
+                    there
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn test_incompatible_units() {
+        assert!(matches!(
+            convert(1.0, &Unit::Cups, &Unit::Grams),
+            Err(UnitError::Incompatible {
+                from_dim: Dimension::Volume,
+                to_dim: Dimension::Mass,
+                ..
+            })
+        ));
+        assert!(Mass::new(1.0, &Unit::Liters).is_err());
+        assert!(Temperature::new(1.0, &Unit::Watts).is_err());
+    }
+}
@@ -0,0 +1,472 @@
+//! Binary SID bundle
+//!
+//! Walking hundreds of JSON files at every `saffron check` is slow, so release
+//! builds pack the database into one file that can be memory-mapped or
+//! embedded with `include_bytes!`. Lookups by ID binary-search a fixed-size
+//! index and borrow straight from the buffer. A client opened with
+//! [`SidClient::from_bundle`] decodes an entry the first time it is asked for
+//! and keeps it, so a compile touching a dozen ingredients decodes a dozen.
+//! Development keeps using the JSON directories, which [`SidClient::open`]
+//! accepts as well.
+//!
+//! Entries stay in their JSON schema form rather than a denser binary
+//! encoding. Optional fields are omitted when absent, so the payload must be
+//! self-describing, and JSON is the form `diff` and provenance paths already
+//! work in. Only the entries a run uses are parsed, so their cost is small
+//! next to walking and reading every file of the directory.
+//!
+//! Layout, all integers little-endian `u32` unless noted:
+//!
+//! ```text
+//! 0   magic "SIDB"
+//! 4   format version (u16), reserved (u16)
+//! 8   entry count
+//! 12  dataset version length, then the UTF-8 version (empty if unversioned)
+//! ..  index: one record per entry, sorted by ID
+//!         id offset, id length, entry offset, entry length
+//! ..  IDs and entries; each entry is compact JSON in schema form
+//! ```
+//!
+//! Offsets are from the start of the bundle.
+
+use crate::{IngredientEntry, LoadError, SidClient};
+use memmap2::Mmap;
+use std::fs::File;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::OnceLock;
+use thiserror::Error;
+
+const MAGIC: &[u8; 4] = b"SIDB";
+
+/// Bumped whenever the layout changes
+pub const FORMAT_VERSION: u16 = 1;
+
+const HEADER_LEN: usize = 16;
+const RECORD_LEN: usize = 16;
+
+#[derive(Debug, Error)]
+pub enum BundleError {
+    #[error("cannot read bundle: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("not a SID bundle")]
+    BadMagic,
+    #[error("bundle format {found} is not supported (expected {FORMAT_VERSION})")]
+    UnsupportedFormat { found: u16 },
+    #[error("bundle is corrupt: {0}")]
+    Corrupt(&'static str),
+    #[error("{} file(s) failed to load, first: {}", .0.len(), .0[0])]
+    Directory(Vec<LoadError>),
+    #[error("entry `{id}` in bundle: {source}")]
+    Entry {
+        id: String,
+        source: serde_json::Error,
+    },
+}
+
+/// Where the bundle's bytes live
+enum Bytes {
+    Static(&'static [u8]),
+    Mapped(Mmap),
+    Owned(Vec<u8>),
+}
+
+/// A validated SID bundle
+pub struct Bundle {
+    bytes: Bytes,
+    count: usize,
+    version: Range<usize>,
+}
+
+impl Bundle {
+    /// Memory-map a bundle file
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, BundleError> {
+        let file = File::open(path)?;
+        // SAFETY: the map is read-only. Bundles are build artifacts that are
+        // replaced, not edited in place, while the compiler runs.
+        let map = unsafe { Mmap::map(&file)? };
+        Self::new(Bytes::Mapped(map))
+    }
+
+    /// A bundle compiled into the binary with `include_bytes!`
+    pub fn from_static(bytes: &'static [u8]) -> Result<Self, BundleError> {
+        Self::new(Bytes::Static(bytes))
+    }
+
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, BundleError> {
+        Self::new(Bytes::Owned(bytes))
+    }
+
+    /// Check the header and every index record once, so lookups need not
+    fn new(bytes: Bytes) -> Result<Self, BundleError> {
+        let data: &[u8] = match &bytes {
+            Bytes::Static(bytes) => bytes,
+            Bytes::Mapped(map) => map,
+            Bytes::Owned(bytes) => bytes,
+        };
+        if data.len() < HEADER_LEN || &data[..4] != MAGIC {
+            return Err(BundleError::BadMagic);
+        }
+        let format = u16::from_le_bytes([data[4], data[5]]);
+        if format != FORMAT_VERSION {
+            return Err(BundleError::UnsupportedFormat { found: format });
+        }
+        let count = read_u32(data, 8) as usize;
+        let version_len = read_u32(data, 12) as usize;
+        let version = HEADER_LEN..HEADER_LEN + version_len;
+        let index_end = count
+            .checked_mul(RECORD_LEN)
+            .and_then(|len| len.checked_add(version.end))
+            .filter(|&end| end <= data.len())
+            .ok_or(BundleError::Corrupt("index extends past the end"))?;
+        if std::str::from_utf8(&data[version.clone()]).is_err() {
+            return Err(BundleError::Corrupt("dataset version is not UTF-8"));
+        }
+
+        let mut previous: Option<&str> = None;
+        for i in 0..count {
+            let (id, entry) = record(data, version.end, i);
+            if id.start < index_end
+                || entry.start < index_end
+                || id.end > data.len()
+                || entry.end > data.len()
+            {
+                return Err(BundleError::Corrupt("record points past the end"));
+            }
+            let id = std::str::from_utf8(&data[id])
+                .map_err(|_| BundleError::Corrupt("ID is not UTF-8"))?;
+            if previous.is_some_and(|previous| previous >= id) {
+                return Err(BundleError::Corrupt("index is not sorted by ID"));
+            }
+            previous = Some(id);
+        }
+
+        Ok(Self {
+            bytes,
+            count,
+            version,
+        })
+    }
+
+    fn data(&self) -> &[u8] {
+        match &self.bytes {
+            Bytes::Static(bytes) => bytes,
+            Bytes::Mapped(map) => map,
+            Bytes::Owned(bytes) => bytes,
+        }
+    }
+
+    /// Dataset version the bundle was built from
+    pub fn version(&self) -> Option<&str> {
+        let version = self.str(self.version.clone());
+        (!version.is_empty()).then_some(version)
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    fn str(&self, range: Range<usize>) -> &str {
+        std::str::from_utf8(&self.data()[range]).expect("validated when the bundle was opened")
+    }
+
+    fn id(&self, i: usize) -> &str {
+        self.str(record(self.data(), self.version.end, i).0)
+    }
+
+    /// Every ID, in order, borrowed from the bundle
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        (0..self.count).map(|i| self.id(i))
+    }
+
+    /// Index record holding `id`
+    fn position(&self, id: &str) -> Option<usize> {
+        let mut range = 0..self.count;
+        // Binary search over the index without building a slice of records
+        while !range.is_empty() {
+            let mid = range.start + range.len() / 2;
+            match self.id(mid).cmp(id) {
+                std::cmp::Ordering::Less => range.start = mid + 1,
+                std::cmp::Ordering::Greater => range.end = mid,
+                std::cmp::Ordering::Equal => return Some(mid),
+            }
+        }
+        None
+    }
+
+    fn raw_at(&self, i: usize) -> &[u8] {
+        &self.data()[record(self.data(), self.version.end, i).1]
+    }
+
+    fn decode(&self, i: usize) -> Result<IngredientEntry, BundleError> {
+        serde_json::from_slice(self.raw_at(i)).map_err(|source| BundleError::Entry {
+            id: self.id(i).to_string(),
+            source,
+        })
+    }
+
+    /// The encoded entry for `id`, borrowed from the bundle
+    pub fn raw(&self, id: &str) -> Option<&[u8]> {
+        self.position(id).map(|i| self.raw_at(i))
+    }
+
+    /// Decode the entry for `id`
+    pub fn get(&self, id: &str) -> Option<Result<IngredientEntry, BundleError>> {
+        self.position(id).map(|i| self.decode(i))
+    }
+}
+
+/// A bundle whose entries are decoded on first use and then kept
+pub(crate) struct LazyEntries {
+    bundle: Bundle,
+    /// One slot per index record; `None` once an entry failed to decode
+    decoded: Vec<OnceLock<Option<IngredientEntry>>>,
+}
+
+impl LazyEntries {
+    fn new(bundle: Bundle) -> Self {
+        let decoded = (0..bundle.len()).map(|_| OnceLock::new()).collect();
+        Self { bundle, decoded }
+    }
+
+    fn at(&self, i: usize) -> Option<&IngredientEntry> {
+        self.decoded[i]
+            .get_or_init(|| self.bundle.decode(i).ok())
+            .as_ref()
+    }
+
+    pub(crate) fn get(&self, id: &str) -> Option<&IngredientEntry> {
+        self.at(self.bundle.position(id)?)
+    }
+
+    pub(crate) fn contains(&self, id: &str) -> bool {
+        self.bundle.position(id).is_some()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.bundle.len()
+    }
+
+    /// Every entry in ID order, decoding those not used yet
+    pub(crate) fn entries(&self) -> impl Iterator<Item = &IngredientEntry> {
+        (0..self.bundle.len()).filter_map(|i| self.at(i))
+    }
+}
+
+fn read_u32(data: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([data[at], data[at + 1], data[at + 2], data[at + 3]])
+}
+
+/// ID and entry byte ranges of index record `i`
+fn record(data: &[u8], index: usize, i: usize) -> (Range<usize>, Range<usize>) {
+    let at = index + i * RECORD_LEN;
+    let field = |n: usize| read_u32(data, at + 4 * n) as usize;
+    let (id, id_len, entry, entry_len) = (field(0), field(1), field(2), field(3));
+    (id..id + id_len, entry..entry + entry_len)
+}
+
+impl SidClient {
+    /// Encode every loaded entry as a bundle
+    pub fn to_bundle(&self) -> Vec<u8> {
+        let mut entries: Vec<_> = self.entries().collect();
+        entries.sort_by(|a, b| a.id.cmp(&b.id));
+        let version = self.version.as_deref().unwrap_or_default();
+
+        let mut out = Vec::new();
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes());
+        out.extend_from_slice(&len_u32(entries.len()).to_le_bytes());
+        out.extend_from_slice(&len_u32(version.len()).to_le_bytes());
+        out.extend_from_slice(version.as_bytes());
+
+        let index = out.len();
+        out.resize(index + entries.len() * RECORD_LEN, 0);
+        for (i, entry) in entries.into_iter().enumerate() {
+            let id_offset = out.len();
+            out.extend_from_slice(entry.id.as_bytes());
+            let entry_offset = out.len();
+            serde_json::to_writer(&mut out, entry).expect("ingredient entries serialize to JSON");
+            let fields = [
+                id_offset,
+                entry.id.len(),
+                entry_offset,
+                out.len() - entry_offset,
+            ];
+            for (n, value) in fields.into_iter().enumerate() {
+                let at = index + i * RECORD_LEN + 4 * n;
+                out[at..at + 4].copy_from_slice(&len_u32(value).to_le_bytes());
+            }
+        }
+        out
+    }
+
+    /// A client backed by `bundle`: [`SidClient::get`] decodes an entry on
+    /// first use and keeps it. An entry that fails to decode reads as absent;
+    /// [`SidClient::load_bundle`] reports such errors up front instead.
+    pub fn from_bundle(bundle: Bundle) -> Self {
+        let mut client = SidClient::new();
+        client.version = bundle.version().map(str::to_string);
+        client.bundle = Some(LazyEntries::new(bundle));
+        client
+    }
+
+    /// Decode every entry of `bundle` and take its dataset version
+    pub fn load_bundle(&mut self, bundle: &Bundle) -> Result<usize, BundleError> {
+        for id in bundle.ids() {
+            let entry = bundle.get(id).expect("ID comes from the index")?;
+            self.field_layers.remove(id);
+            self.ingredients.insert(entry.id.clone(), entry);
+        }
+        self.version = bundle.version().map(str::to_string);
+        self.reindex();
+        Ok(bundle.len())
+    }
+
+    /// Load a bundle file, or a JSON data directory during development
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, BundleError> {
+        let path = path.as_ref();
+        if path.is_dir() {
+            let mut client = SidClient::new();
+            client.load_dir(path).map_err(BundleError::Directory)?;
+            Ok(client)
+        } else {
+            Ok(Self::from_bundle(Bundle::open(path)?))
+        }
+    }
+}
+
+/// Bundles are far below 4 GiB; anything larger is a bug in the data
+fn len_u32(len: usize) -> u32 {
+    u32::try_from(len).expect("SID bundle exceeds 4 GiB")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data_dir, repository};
+    use std::fs;
+
+    #[test]
+    fn test_round_trip() {
+        let client = repository();
+        let bundle = Bundle::from_bytes(client.to_bundle()).unwrap();
+        assert_eq!(bundle.len(), client.count());
+        assert_eq!(bundle.version(), Some("0.1.0"));
+        assert!(bundle.ids().is_sorted());
+
+        let raw = bundle.raw("chicken_egg").unwrap();
+        assert!(raw.starts_with(br#"{"id":"chicken_egg""#));
+        assert!(bundle.raw("duck_egg").is_none());
+        let egg = bundle.get("chicken_egg").unwrap().unwrap();
+        assert_eq!(egg.composition.protein, 12.56);
+
+        let mut loaded = SidClient::new();
+        assert_eq!(loaded.load_bundle(&bundle).unwrap(), client.count());
+        // Same entries and dataset version, so results cite the same SID
+        assert_eq!(loaded.version(), client.version());
+        assert_eq!(loaded.search("huevo")[0].id, "chicken_egg");
+    }
+
+    #[test]
+    fn test_lazy_client() {
+        let json = repository();
+        let client = SidClient::from_bundle(Bundle::from_bytes(json.to_bundle()).unwrap());
+        let decoded = |client: &SidClient| {
+            client
+                .bundle
+                .as_ref()
+                .unwrap()
+                .decoded
+                .iter()
+                .filter(|slot| slot.get().is_some())
+                .count()
+        };
+        assert_eq!(client.count(), json.count());
+        assert_eq!(decoded(&client), 0);
+
+        let egg = client.get("chicken_egg").unwrap();
+        assert_eq!(egg.composition.protein, 12.56);
+        // Cached: the same decoded entry comes back
+        assert!(std::ptr::eq(egg, client.get("chicken_egg").unwrap()));
+        assert!(client.get("duck_egg").is_none());
+        assert_eq!(decoded(&client), 1);
+
+        assert_eq!(client.search("huevo")[0].id, "chicken_egg");
+        assert!(json.diff(&client).is_empty());
+        assert_eq!(client.version(), json.version());
+    }
+
+    #[test]
+    fn test_open_file_or_directory() {
+        let path = std::env::temp_dir().join(format!("saffron-sid-{}.bundle", std::process::id()));
+        fs::write(&path, repository().to_bundle()).unwrap();
+        let mapped = SidClient::open(&path).unwrap();
+        let json = SidClient::open(data_dir()).unwrap();
+        assert_eq!(mapped.version(), json.version());
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            SidClient::open(data_dir().join("missing.bundle")),
+            Err(BundleError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_overlay_on_bundle() {
+        let bundle = Bundle::from_bytes(repository().to_bundle()).unwrap();
+        let dir = std::env::temp_dir().join(format!("saffron-sid-bundle-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("VERSION"), "9.9.9").unwrap();
+        fs::write(
+            dir.join("salt.json"),
+            r#"{ "id": "table_salt", "subcategory": "flaky" }"#,
+        )
+        .unwrap();
+
+        let mut client = SidClient::from_bundle(bundle);
+        client.add_layer("project", &dir).unwrap();
+        assert_eq!(
+            client.get("table_salt").unwrap().subcategory.as_deref(),
+            Some("flaky")
+        );
+        assert_eq!(
+            client.field_layer("table_salt", "subcategory"),
+            Some("project")
+        );
+        assert_eq!(client.version().version.as_deref(), Some("0.1.0"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_invalid_bundles() {
+        assert!(matches!(
+            Bundle::from_bytes(b"{\"id\": 1}".to_vec()),
+            Err(BundleError::BadMagic)
+        ));
+
+        let mut bytes = repository().to_bundle();
+        bytes[4] = 2;
+        assert!(matches!(
+            Bundle::from_bytes(bytes.clone()),
+            Err(BundleError::UnsupportedFormat { found: 2 })
+        ));
+        bytes[4] = 1;
+        bytes.truncate(40);
+        assert!(matches!(
+            Bundle::from_bytes(bytes),
+            Err(BundleError::Corrupt(_))
+        ));
+
+        let empty =
+            Bundle::from_static(&[b'S', b'I', b'D', b'B', 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0])
+                .unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.version(), None);
+    }
+}
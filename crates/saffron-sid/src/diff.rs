@@ -0,0 +1,213 @@
+//! Differences between two SID snapshots
+//!
+//! Entries are compared field by field through their JSON form, so a change
+//! is reported at the dotted path the schema and `provenance` use
+//! (`composition.minerals.sodium`). Lists such as `allergens` are compared
+//! as a whole.
+
+use crate::provenance::SidVersion;
+use crate::SidClient;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+
+/// One field whose value differs; `None` when the field is absent on that side
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// An entry present in both snapshots with different contents
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EntryDiff {
+    pub id: String,
+    pub revision_before: u32,
+    pub revision_after: u32,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Everything that changed from one snapshot to another, ordered by id
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SidDiff {
+    pub from: SidVersion,
+    pub to: SidVersion,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<EntryDiff>,
+}
+
+impl SidDiff {
+    /// Whether the snapshots hold the same entries
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl fmt::Display for SidDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "SID {} -> {}", self.from, self.to)?;
+        for id in &self.added {
+            writeln!(f, "+ {id}")?;
+        }
+        for id in &self.removed {
+            writeln!(f, "- {id}")?;
+        }
+        for entry in &self.changed {
+            writeln!(
+                f,
+                "~ {} (revision {} -> {})",
+                entry.id, entry.revision_before, entry.revision_after
+            )?;
+            for change in &entry.changes {
+                let show = |value: &Option<Value>| {
+                    value
+                        .as_ref()
+                        .map_or_else(|| "(absent)".to_string(), Value::to_string)
+                };
+                writeln!(
+                    f,
+                    "    {}: {} -> {}",
+                    change.field,
+                    show(&change.before),
+                    show(&change.after)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SidClient {
+    /// What changed from this snapshot to `newer`
+    pub fn diff(&self, newer: &SidClient) -> SidDiff {
+        let mut added: Vec<String> = newer
+            .entries()
+            .filter(|entry| self.get(&entry.id).is_none())
+            .map(|entry| entry.id.clone())
+            .collect();
+        let mut removed: Vec<String> = self
+            .entries()
+            .filter(|entry| newer.get(&entry.id).is_none())
+            .map(|entry| entry.id.clone())
+            .collect();
+        added.sort();
+        removed.sort();
+
+        let mut changed: Vec<EntryDiff> = self
+            .entries()
+            .filter_map(|before| {
+                let after = newer.get(&before.id)?;
+                let mut changes = Vec::new();
+                compare(
+                    "",
+                    serde_json::to_value(before).ok(),
+                    serde_json::to_value(after).ok(),
+                    &mut changes,
+                );
+                (!changes.is_empty()).then(|| EntryDiff {
+                    id: before.id.clone(),
+                    revision_before: before.revision(),
+                    revision_after: after.revision(),
+                    changes,
+                })
+            })
+            .collect();
+        changed.sort_by(|a, b| a.id.cmp(&b.id));
+
+        SidDiff {
+            from: self.version(),
+            to: newer.version(),
+            added,
+            removed,
+            changed,
+        }
+    }
+}
+
+/// Recurse into objects present on both sides, report anything else that differs
+fn compare(path: &str, before: Option<Value>, after: Option<Value>, out: &mut Vec<FieldChange>) {
+    match (before, after) {
+        (Some(Value::Object(mut before)), Some(Value::Object(mut after))) => {
+            let mut keys: Vec<String> = before.keys().chain(after.keys()).cloned().collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let field = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                compare(&field, before.remove(&key), after.remove(&key), out);
+            }
+        }
+        (before, after) if before != after => out.push(FieldChange {
+            field: path.to_string(),
+            before,
+            after,
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::provenance::Revision;
+    use crate::repository;
+
+    #[test]
+    fn test_identical_snapshots() {
+        let diff = repository().diff(&repository());
+        assert!(diff.is_empty());
+        assert_eq!(diff.from, diff.to);
+    }
+
+    #[test]
+    fn test_field_level_changes() {
+        let old = repository();
+        let mut new = repository();
+        new.set_version("0.2.0");
+
+        let mut egg = old.get("chicken_egg").unwrap().clone();
+        egg.composition.protein = 12.6;
+        egg.composition.minerals.insert("selenium".into(), 0.03);
+        egg.revisions.push(Revision {
+            revision: egg.revision() + 1,
+            date: "2026-10-18".into(),
+            summary: "USDA 2026 release".into(),
+        });
+        let mut duck = egg.clone();
+        duck.id = "duck_egg".into();
+        new.load_json(&serde_json::to_string(&[egg, duck]).unwrap())
+            .unwrap();
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.added, ["duck_egg"]);
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.to.version.as_deref(), Some("0.2.0"));
+        assert_ne!(diff.from.fingerprint, diff.to.fingerprint);
+
+        let entry = &diff.changed[0];
+        assert_eq!(entry.id, "chicken_egg");
+        assert_eq!(entry.revision_after, entry.revision_before + 1);
+        let fields: Vec<&str> = entry.changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            [
+                "composition.minerals.selenium",
+                "composition.protein",
+                "revisions"
+            ]
+        );
+        assert_eq!(entry.changes[0].before, None);
+        assert_eq!(entry.changes[1].after, Some(12.6.into()));
+
+        let text = diff.to_string();
+        assert!(text.contains("+ duck_egg"));
+        assert!(text.contains("composition.protein: 12.56 -> 12.6"));
+
+        let reverse = new.diff(&old);
+        assert_eq!(reverse.removed, ["duck_egg"]);
+    }
+}
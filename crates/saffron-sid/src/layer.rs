@@ -0,0 +1,276 @@
+//! Layered data directories
+//!
+//! Organizations keep proprietary ingredients, such as a supplier's oil
+//! blend, out of the upstream database by stacking directories on top of it:
+//!
+//! ```no_run
+//! # use saffron_sid::SidClient;
+//! let mut sid = SidClient::new();
+//! sid.add_layer("builtin", "sid/data").unwrap();
+//! sid.add_layer("acme", "/srv/acme/sid").unwrap();
+//! sid.add_layer("project", "sid.local").unwrap();
+//! ```
+//!
+//! Later layers take precedence. An entry whose ID is new must be complete;
+//! one that already exists may list only the fields it changes, which are
+//! merged into the existing entry: objects such as `composition` merge key by
+//! key, anything else (numbers, strings, lists) replaces the lower layer's
+//! value. [`SidClient::field_layer`] reports which layer a value came from.
+
+use crate::{LoadError, SidClient};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// A data directory stacked onto the database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layer {
+    pub name: String,
+    pub dir: PathBuf,
+}
+
+impl SidClient {
+    /// Load `dir` on top of the layers added so far
+    ///
+    /// The layer that starts the database sets the dataset version from its
+    /// `VERSION` file; a bundle loaded first takes that role instead. As
+    /// with [`SidClient::load_dir`], valid entries are loaded even when other
+    /// files fail, and an ID may appear only once within a layer.
+    pub fn add_layer(
+        &mut self,
+        name: impl Into<String>,
+        dir: impl AsRef<Path>,
+    ) -> Result<usize, Vec<LoadError>> {
+        let dir = dir.as_ref();
+        self.layers.push(Layer {
+            name: name.into(),
+            dir: dir.to_path_buf(),
+        });
+        self.load_files(dir, Some(self.layers.len() - 1))
+    }
+
+    /// Layers in order of increasing precedence
+    pub fn layers(&self) -> &[Layer] {
+        &self.layers
+    }
+
+    /// Name of the layer the value at `field` (e.g. `composition.total_fat`) of
+    /// entry `id` came from; `None` for entries not loaded through a layer
+    pub fn field_layer(&self, id: &str, field: &str) -> Option<&str> {
+        let fields = self.field_layers.get(id)?;
+        let layer = fields
+            .iter()
+            .filter(|(key, _)| {
+                key.is_empty()
+                    || field == key.as_str()
+                    || field
+                        .strip_prefix(key.as_str())
+                        .is_some_and(|rest| rest.starts_with('.'))
+            })
+            .max_by_key(|(key, _)| key.len())
+            .map(|(_, &layer)| layer)?;
+        Some(&self.layers[layer].name)
+    }
+
+    /// Layer of every field of entry `id`, keyed by dotted path
+    pub fn field_layers(&self, id: &str) -> BTreeMap<String, &str> {
+        let Some(entry) = self.get(id) else {
+            return BTreeMap::new();
+        };
+        let mut fields = Vec::new();
+        if let Ok(value) = serde_json::to_value(entry) {
+            leaf_fields(&value, "", &mut fields);
+        }
+        fields
+            .into_iter()
+            .filter_map(|field| {
+                let layer = self.field_layer(id, &field)?;
+                Some((field, layer))
+            })
+            .collect()
+    }
+}
+
+/// Merge `overlay` into `base`: objects key by key, anything else replaces
+pub(crate) fn merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Attribute every value `overlay` sets to `layer`
+pub(crate) fn record_overrides(
+    fields: &mut BTreeMap<String, usize>,
+    overlay: &Value,
+    layer: usize,
+) {
+    let mut paths = Vec::new();
+    leaf_fields(overlay, "", &mut paths);
+    for path in paths.into_iter().filter(|path| path != "id") {
+        // A replaced value takes whatever was nested below it along
+        let nested = format!("{path}.");
+        fields.retain(|key, _| !key.starts_with(&nested));
+        fields.insert(path, layer);
+    }
+}
+
+/// Dotted paths of every non-object value
+fn leaf_fields(value: &Value, prefix: &str, out: &mut Vec<String>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                leaf_fields(value, &path, out);
+            }
+        }
+        _ if !prefix.is_empty() => out.push(prefix.to_string()),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data_dir, TestEntry};
+    use std::fs;
+
+    /// Fresh scratch directory under the system temp dir
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("saffron-sid-layer-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("fats")).unwrap();
+        dir
+    }
+
+    fn blend() -> String {
+        TestEntry::new("acme_frying_blend", "fat")
+            .with("name.en", "ACME Frying Blend")
+            .with("composition.total_fat", 100.0)
+            .with("composition.saturated_fat", 9.0)
+            .with("physical.density_g_per_ml", 0.92)
+            .with("physical.smoke_point_celsius", 240.0)
+            .with("sources", vec!["ACME specification sheet FB-7"])
+            .json()
+    }
+
+    #[test]
+    fn test_layers_override_per_field() {
+        let org = scratch_dir("org");
+        fs::write(org.join("fats/blend.json"), blend()).unwrap();
+        fs::write(
+            org.join("fats/sunflower_oil.json"),
+            r#"{ "id": "sunflower_oil", "physical": { "smoke_point_celsius": 225.0 } }"#,
+        )
+        .unwrap();
+        let project = scratch_dir("project");
+        fs::write(
+            project.join("fats/sunflower_oil.json"),
+            r#"{ "id": "sunflower_oil", "physical": { "smoke_point_celsius": 210.0 },
+                 "substitutes": ["acme_frying_blend"] }"#,
+        )
+        .unwrap();
+
+        let mut client = SidClient::new();
+        let builtin_count = client.add_layer("builtin", data_dir()).unwrap();
+        assert_eq!(client.add_layer("org", &org).unwrap(), 2);
+        assert_eq!(client.add_layer("project", &project).unwrap(), 1);
+        assert_eq!(client.count(), builtin_count + 1);
+        assert_eq!(client.layers()[1].name, "org");
+
+        let oil = client.get("sunflower_oil").unwrap();
+        assert_eq!(oil.physical.smoke_point_celsius, Some(210.0));
+        assert_eq!(oil.physical.density_g_per_ml, Some(0.92));
+        assert_eq!(oil.substitutes, ["acme_frying_blend"]);
+
+        assert_eq!(
+            client.field_layer("sunflower_oil", "physical.smoke_point_celsius"),
+            Some("project")
+        );
+        assert_eq!(
+            client.field_layer("sunflower_oil", "composition.total_fat"),
+            Some("builtin")
+        );
+        assert_eq!(
+            client.field_layer("acme_frying_blend", "physical.density_g_per_ml"),
+            Some("org")
+        );
+        let layers = client.field_layers("sunflower_oil");
+        assert_eq!(layers["substitutes"], "project");
+        assert_eq!(layers["name.en"], "builtin");
+        // Only the first layer versions the dataset
+        assert_eq!(client.version().version.as_deref(), Some("0.1.0"));
+        assert!(client
+            .origin("sunflower_oil")
+            .unwrap()
+            .starts_with(&project));
+
+        fs::remove_dir_all(&org).unwrap();
+        fs::remove_dir_all(&project).unwrap();
+    }
+
+    #[test]
+    fn test_layer_errors() {
+        let org = scratch_dir("errors");
+        // New IDs must be complete entries
+        fs::write(
+            org.join("fats/partial.json"),
+            r#"{ "id": "acme_blend", "physical": {} }"#,
+        )
+        .unwrap();
+        // An override must still produce a valid entry
+        fs::write(
+            org.join("fats/typo.json"),
+            r#"{ "id": "sunflower_oil", "physical": { "smoke_point": 225.0 } }"#,
+        )
+        .unwrap();
+        fs::write(org.join("fats/a.json"), blend()).unwrap();
+        fs::write(org.join("fats/b.json"), blend()).unwrap();
+
+        let mut client = SidClient::new();
+        client.add_layer("builtin", data_dir()).unwrap();
+        let errors = client.add_layer("org", &org).unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert!(errors.iter().any(|e| matches!(
+            e,
+            LoadError::DuplicateId { id, first, .. } if id == "acme_frying_blend" && first.ends_with("a.json")
+        )));
+        assert_eq!(
+            client
+                .get("sunflower_oil")
+                .unwrap()
+                .physical
+                .smoke_point_celsius,
+            Some(232.0)
+        );
+        assert!(client.get("acme_blend").is_none());
+        fs::remove_dir_all(&org).unwrap();
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut base = serde_json::json!({ "a": { "b": 1, "c": [1, 2] }, "d": 1 });
+        merge(
+            &mut base,
+            serde_json::json!({ "a": { "c": [3] }, "e": true }),
+        );
+        assert_eq!(
+            base,
+            serde_json::json!({ "a": { "b": 1, "c": [3] }, "d": 1, "e": true })
+        );
+    }
+}
@@ -3,11 +3,31 @@
 //! Client library for querying the ingredient database.
 //! The SID contains physical and chemical properties for all known ingredients.
 
+use bundle::LazyEntries;
+use layer::Layer;
+use provenance::Revision;
+pub use saffron_ast::{Allergen, IngredientCategory, Month};
+use search::{SearchHit, SearchIndex, SearchQuery};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use thiserror::Error;
+
+pub mod bundle;
+pub mod diff;
+pub mod layer;
+pub mod provenance;
+pub mod query;
+pub mod resolve;
+pub mod search;
+pub mod substitute;
+pub mod validation;
 
 /// Multilingual name
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct LocalizedName {
     pub en: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -22,6 +42,7 @@ pub struct LocalizedName {
 
 /// Chemical composition per 100g
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Composition {
     pub water: f64,
     pub protein: f64,
@@ -39,6 +60,7 @@ pub struct Composition {
 
 /// Physical properties
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct PhysicalProperties {
     pub density_g_per_ml: Option<f64>,
     pub boiling_point_celsius: Option<f64>,
@@ -48,26 +70,143 @@ pub struct PhysicalProperties {
     pub flash_point_celsius: Option<f64>,
 }
 
+/// Arrhenius denaturation parameters of one protein fraction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProteinKinetics {
+    /// Fraction name, e.g. `myosin` or `ovalbumin`
+    pub protein: String,
+    /// Share of the total protein
+    pub share: f64,
+    pub activation_energy_j_per_mol: f64,
+    pub reference_temp_c: f64,
+    /// Rate constant at `reference_temp_c`
+    pub reference_rate_per_s: f64,
+}
+
+/// Protein denaturation model of an ingredient or one of its parts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DenaturationProfile {
+    /// Part the profile covers, e.g. `white` or `yolk` of an egg; absent for the whole ingredient
+    pub part: Option<String>,
+    /// `meat`, `egg_white` or `egg_yolk`: how doneness and texture are read
+    pub system: String,
+    pub fractions: Vec<ProteinKinetics>,
+}
+
+/// Dietary suitability
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Diet {
+    Vegan,
+    Vegetarian,
+}
+
+/// A recipe type name that resolves to an entry, e.g. `Egg(type: .Chicken)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Alias {
+    #[serde(rename = "type")]
+    pub type_name: String,
+    /// Discriminating parameters and the enum variant each must have
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, String>,
+}
+
 /// A complete ingredient entry in the SID
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct IngredientEntry {
     pub id: String,
     pub name: LocalizedName,
-    pub category: String,
+    pub category: IngredientCategory,
     pub subcategory: Option<String>,
     pub composition: Composition,
     pub physical: PhysicalProperties,
+    /// Protein denaturation kinetics, per part where the ingredient has several
+    #[serde(default)]
+    pub denaturation: Vec<DenaturationProfile>,
     #[serde(default)]
-    pub allergens: Vec<String>,
+    pub allergens: Vec<Allergen>,
+    /// Diets the ingredient is suitable for
+    #[serde(default)]
+    pub diets: Vec<Diet>,
+    /// IDs of other entries that can replace this one
     #[serde(default)]
     pub substitutes: Vec<String>,
+    /// Recipe type names that resolve to this entry
+    #[serde(default)]
+    pub aliases: Vec<Alias>,
+    /// Months the ingredient is in season
+    #[serde(default)]
+    pub seasonal_availability: Vec<Month>,
     #[serde(default)]
     pub sources: Vec<String>,
+    /// Source of individual fields by dotted path, each one of `sources`
+    #[serde(default)]
+    pub provenance: BTreeMap<String, String>,
+    /// Edits to this entry, oldest first
+    #[serde(default)]
+    pub revisions: Vec<Revision>,
+}
+
+/// A problem with one file while loading a data directory
+#[derive(Debug, Error)]
+pub enum LoadError {
+    #[error("{path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("{path}: {source}")]
+    Parse {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[error("{path}: duplicate id `{id}` (already loaded from {first})")]
+    DuplicateId {
+        path: PathBuf,
+        id: String,
+        first: PathBuf,
+    },
+    #[error("{path}: category `{category}` does not match directory `{directory}`")]
+    CategoryMismatch {
+        path: PathBuf,
+        category: &'static str,
+        directory: String,
+    },
+}
+
+impl LoadError {
+    /// The file the error refers to
+    pub fn path(&self) -> &Path {
+        match self {
+            LoadError::Io { path, .. }
+            | LoadError::Parse { path, .. }
+            | LoadError::DuplicateId { path, .. }
+            | LoadError::CategoryMismatch { path, .. } => path,
+        }
+    }
 }
 
 /// The SID client for querying ingredients
 pub struct SidClient {
+    /// Entries loaded from JSON, shadowing any bundle entry with the same ID
     ingredients: HashMap<String, IngredientEntry>,
+    /// Bundle opened with [`SidClient::from_bundle`], decoded entry by entry on use
+    bundle: Option<LazyEntries>,
+    /// File each entry was loaded from; absent for entries loaded from strings
+    origins: HashMap<String, PathBuf>,
+    /// Built on the first search after a load
+    index: OnceLock<SearchIndex>,
+    /// Dataset release from the `VERSION` file of the data directory
+    version: Option<String>,
+    /// Overlay directories, lowest precedence first
+    layers: Vec<Layer>,
+    /// Layer index each entry and overridden field came from, keyed by dotted
+    /// path with `""` for the layer that defined the entry
+    field_layers: HashMap<String, BTreeMap<String, usize>>,
 }
 
 impl SidClient {
@@ -75,6 +214,146 @@ impl SidClient {
     pub fn new() -> Self {
         Self {
             ingredients: HashMap::new(),
+            bundle: None,
+            origins: HashMap::new(),
+            index: OnceLock::new(),
+            version: None,
+            layers: Vec::new(),
+            field_layers: HashMap::new(),
+        }
+    }
+
+    /// Load every `*.json` file under `dir`, e.g. `sid/data`
+    ///
+    /// Files may hold a single entry or an array. Entries in a category
+    /// subdirectory must have that category (`proteins/` or `protein/` for
+    /// `"protein"`). Valid entries are loaded even when other files fail;
+    /// the returned errors name the offending file. A `VERSION` file at the
+    /// top of `dir` sets the dataset version.
+    pub fn load_dir(&mut self, dir: impl AsRef<Path>) -> Result<usize, Vec<LoadError>> {
+        self.load_files(dir.as_ref(), None)
+    }
+
+    /// Load a data directory; entries of a `layer` may override earlier ones
+    fn load_files(&mut self, dir: &Path, layer: Option<usize>) -> Result<usize, Vec<LoadError>> {
+        let mut files = Vec::new();
+        let mut errors = Vec::new();
+        let version_file = dir.join("VERSION");
+        // Overlays extend the dataset rather than release it
+        if (layer.is_none() || self.count() == 0) && version_file.is_file() {
+            match fs::read_to_string(&version_file) {
+                Ok(version) => self.version = Some(version.trim().to_string()),
+                Err(source) => errors.push(LoadError::Io {
+                    path: version_file,
+                    source,
+                }),
+            }
+        }
+        collect_json_files(dir, &mut files, &mut errors);
+        files.sort();
+
+        let mut loaded = 0;
+        let mut seen: HashMap<String, PathBuf> = HashMap::new();
+        for path in files {
+            let values = match fs::read_to_string(&path) {
+                Ok(text) => match parse_data_file(&text) {
+                    Ok(values) => values,
+                    Err(source) => {
+                        errors.push(LoadError::Parse { path, source });
+                        continue;
+                    }
+                },
+                Err(source) => {
+                    errors.push(LoadError::Io { path, source });
+                    continue;
+                }
+            };
+
+            let directory = path
+                .parent()
+                .filter(|parent| *parent != dir)
+                .and_then(Path::file_name)
+                .map(|name| name.to_string_lossy().into_owned());
+            for value in values {
+                let id = match serde_json::from_value::<EntryId>(value.clone()) {
+                    Ok(EntryId { id }) => id,
+                    Err(source) => {
+                        errors.push(LoadError::Parse {
+                            path: path.clone(),
+                            source,
+                        });
+                        continue;
+                    }
+                };
+                let overrides = layer.is_some() && !seen.contains_key(&id);
+                let parsed = match self.get(&id) {
+                    Some(base) if overrides => {
+                        let mut merged = serde_json::to_value(base)
+                            .expect("ingredient entries serialize to JSON");
+                        layer::merge(&mut merged, value.clone());
+                        serde_json::from_value(merged)
+                    }
+                    Some(_) => {
+                        let first = seen
+                            .get(&id)
+                            .or_else(|| self.origins.get(&id))
+                            .cloned()
+                            .unwrap_or_else(|| PathBuf::from("<json>"));
+                        errors.push(LoadError::DuplicateId {
+                            path: path.clone(),
+                            id,
+                            first,
+                        });
+                        continue;
+                    }
+                    None => serde_json::from_value(value.clone()),
+                };
+                let entry: IngredientEntry = match parsed {
+                    Ok(entry) => entry,
+                    Err(source) => {
+                        errors.push(LoadError::Parse {
+                            path: path.clone(),
+                            source,
+                        });
+                        continue;
+                    }
+                };
+
+                if let Some(directory) = &directory {
+                    let category = entry.category.as_str();
+                    let plural = format!("{category}s");
+                    if directory != category && *directory != plural {
+                        errors.push(LoadError::CategoryMismatch {
+                            path: path.clone(),
+                            category,
+                            directory: directory.clone(),
+                        });
+                        continue;
+                    }
+                }
+                if let Some(layer) = layer {
+                    let fields = self.field_layers.entry(id.clone()).or_default();
+                    if self.ingredients.contains_key(&id)
+                        || self.bundle.as_ref().is_some_and(|b| b.contains(&id))
+                    {
+                        layer::record_overrides(fields, &value, layer);
+                    } else {
+                        fields.clear();
+                        fields.insert(String::new(), layer);
+                    }
+                }
+                seen.insert(id.clone(), path.clone());
+                self.origins.insert(id.clone(), path.clone());
+                self.ingredients.insert(id, entry);
+                loaded += 1;
+            }
+        }
+
+        self.reindex();
+        if errors.is_empty() {
+            Ok(loaded)
+        } else {
+            Err(errors)
         }
     }
 
@@ -82,69 +361,221 @@ impl SidClient {
     pub fn load_json(&mut self, json: &str) -> Result<(), serde_json::Error> {
         let entries: Vec<IngredientEntry> = serde_json::from_str(json)?;
         for entry in entries {
+            self.field_layers.remove(&entry.id);
             self.ingredients.insert(entry.id.clone(), entry);
         }
+        self.reindex();
         Ok(())
     }
 
     /// Look up an ingredient by ID
     pub fn get(&self, id: &str) -> Option<&IngredientEntry> {
-        self.ingredients.get(id)
+        self.ingredients
+            .get(id)
+            .or_else(|| self.bundle.as_ref()?.get(id))
     }
 
-    /// Search ingredients by name (English)
+    /// Every entry, JSON-loaded first, then the bundle's in ID order
+    pub(crate) fn entries(&self) -> impl Iterator<Item = &IngredientEntry> {
+        let bundled = self.bundle.iter().flat_map(|bundle| {
+            bundle
+                .entries()
+                .filter(|entry| !self.ingredients.contains_key(&entry.id))
+        });
+        self.ingredients.values().chain(bundled)
+    }
+
+    /// File an ingredient was loaded from, if it came from [`SidClient::load_dir`]
+    pub fn origin(&self, id: &str) -> Option<&Path> {
+        self.origins.get(id).map(PathBuf::as_path)
+    }
+
+    /// Search ingredients by name in any language, best match first
     pub fn search(&self, query: &str) -> Vec<&IngredientEntry> {
-        let q = query.to_lowercase();
-        self.ingredients
-            .values()
-            .filter(|e| e.name.en.to_lowercase().contains(&q) || e.id.contains(&q))
+        self.search_with(&SearchQuery::new(query))
+            .iter()
+            .filter_map(|hit| self.get(&hit.id))
             .collect()
     }
 
+    /// Ranked, filtered search; see [`search`] for how matches are scored
+    pub fn search_with(&self, query: &SearchQuery) -> Vec<SearchHit> {
+        self.index
+            .get_or_init(|| SearchIndex::build(self.entries()))
+            .search(query)
+    }
+
+    /// Drop the search index after a load; the next search rebuilds it
+    fn reindex(&mut self) {
+        self.index = OnceLock::new();
+    }
+
     /// Get all ingredients in a category
-    pub fn by_category(&self, category: &str) -> Vec<&IngredientEntry> {
-        self.ingredients
-            .values()
-            .filter(|e| e.category == category)
-            .collect()
+    pub fn by_category(&self, category: IngredientCategory) -> Vec<&IngredientEntry> {
+        self.entries().filter(|e| e.category == category).collect()
     }
 
     /// Total number of ingredients loaded
     pub fn count(&self) -> usize {
-        self.ingredients.len()
+        let bundled = self.bundle.as_ref().map_or(0, |bundle| {
+            bundle.len()
+                - self
+                    .ingredients
+                    .keys()
+                    .filter(|id| bundle.contains(id))
+                    .count()
+        });
+        self.ingredients.len() + bundled
+    }
+}
+
+impl Default for SidClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A data file holds either one entry or an array of entries
+fn parse_data_file(text: &str) -> Result<Vec<serde_json::Value>, serde_json::Error> {
+    match serde_json::from_str(text)? {
+        serde_json::Value::Array(values) => Ok(values),
+        value => Ok(vec![value]),
+    }
+}
+
+/// Every entry, including a partial override, must name its ID
+#[derive(Deserialize)]
+struct EntryId {
+    id: String,
+}
+
+fn collect_json_files(dir: &Path, files: &mut Vec<PathBuf>, errors: &mut Vec<LoadError>) {
+    let read = match fs::read_dir(dir) {
+        Ok(read) => read,
+        Err(source) => {
+            errors.push(LoadError::Io {
+                path: dir.to_path_buf(),
+                source,
+            });
+            return;
+        }
+    };
+    for item in read {
+        match item {
+            Ok(item) => {
+                let path = item.path();
+                if path.is_dir() {
+                    collect_json_files(&path, files, errors);
+                } else if path.extension().is_some_and(|ext| ext == "json") {
+                    files.push(path);
+                }
+            }
+            Err(source) => errors.push(LoadError::Io {
+                path: dir.to_path_buf(),
+                source,
+            }),
+        }
     }
 }
 
+/// The repository's `sid/data` directory
 #[cfg(test)]
-mod tests {
-    use super::*;
+pub(crate) fn data_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../sid/data")
+}
+
+/// Client loaded with the repository's SID data
+#[cfg(test)]
+pub(crate) fn repository() -> SidClient {
+    let mut client = SidClient::new();
+    client.load_dir(data_dir()).unwrap();
+    client
+}
+
+/// Test entry builder: a minimal valid entry whose fields are set by dotted path
+#[cfg(test)]
+#[derive(Debug, Clone)]
+pub(crate) struct TestEntry(serde_json::Value);
 
-    fn sample_json() -> &'static str {
-        r#"[{
-            "id": "chicken_egg",
-            "name": { "en": "Chicken Egg", "es": "Huevo de gallina" },
-            "category": "protein",
-            "subcategory": "egg",
+#[cfg(test)]
+impl TestEntry {
+    pub(crate) fn new(id: &str, category: &str) -> Self {
+        Self(serde_json::json!({
+            "id": id,
+            "name": { "en": id },
+            "category": category,
             "composition": {
-                "water": 76.15, "protein": 12.56, "total_fat": 9.51,
-                "saturated_fat": 3.13, "carbohydrates": 0.72,
-                "fiber": 0.0, "sugar": 0.37, "ph": 7.6
-            },
-            "physical": {
-                "density_g_per_ml": 1.031,
-                "boiling_point_celsius": 100.0,
-                "specific_heat_j_per_g_k": 3.18
+                "water": 0.0, "protein": 0.0, "total_fat": 0.0, "saturated_fat": 0.0,
+                "carbohydrates": 0.0, "fiber": 0.0, "sugar": 0.0
             },
-            "allergens": ["eggs"],
-            "substitutes": ["duck_egg", "quail_egg"],
-            "sources": ["USDA FoodData Central #171287"]
-        }]"#
+            "physical": {},
+            "sources": ["test"]
+        }))
+    }
+
+    /// Set the field at `path`, e.g. `composition.water`
+    pub(crate) fn with(mut self, path: &str, value: impl Into<serde_json::Value>) -> Self {
+        let field = path
+            .split('.')
+            .fold(&mut self.0, |value, key| &mut value[key]);
+        *field = value.into();
+        self
+    }
+
+    pub(crate) fn json(&self) -> String {
+        self.0.to_string()
+    }
+
+    pub(crate) fn entry(&self) -> IngredientEntry {
+        serde_json::from_value(self.0.clone()).unwrap()
+    }
+}
+
+/// JSON array of test entries, as read by [`SidClient::load_json`]
+#[cfg(test)]
+pub(crate) fn json_array(entries: &[TestEntry]) -> String {
+    serde_json::to_string(&entries.iter().map(|e| &e.0).collect::<Vec<_>>()).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample() -> TestEntry {
+        TestEntry::new("chicken_egg", "protein")
+            .with("name.en", "Chicken Egg")
+            .with("name.es", "Huevo de gallina")
+            .with("subcategory", "egg")
+            .with(
+                "composition",
+                json!({
+                    "water": 76.15, "protein": 12.56, "total_fat": 9.51,
+                    "saturated_fat": 3.13, "carbohydrates": 0.72,
+                    "fiber": 0.0, "sugar": 0.37, "ph": 7.6
+                }),
+            )
+            .with(
+                "physical",
+                json!({
+                    "density_g_per_ml": 1.031,
+                    "boiling_point_celsius": 100.0,
+                    "specific_heat_j_per_g_k": 3.18
+                }),
+            )
+            .with("allergens", vec!["eggs"])
+            .with("substitutes", vec!["duck_egg", "quail_egg"])
+            .with("sources", vec!["USDA FoodData Central #171287"])
+    }
+
+    fn sample_json() -> String {
+        json_array(&[sample()])
     }
 
     #[test]
     fn test_load_and_query() {
         let mut client = SidClient::new();
-        client.load_json(sample_json()).unwrap();
+        client.load_json(&sample_json()).unwrap();
         assert_eq!(client.count(), 1);
 
         let egg = client.get("chicken_egg").unwrap();
@@ -152,10 +583,86 @@ mod tests {
         assert_eq!(egg.composition.protein, 12.56);
     }
 
+    /// Fresh scratch directory under the system temp dir
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("saffron-sid-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn sample_entry() -> String {
+        sample().json()
+    }
+
+    #[test]
+    fn test_load_repository_data() {
+        let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../sid/data");
+        let mut client = SidClient::new();
+        let loaded = client.load_dir(&data).unwrap();
+        assert_eq!(loaded, client.count());
+        assert!(client.get("chicken_egg").is_some());
+        assert!(client
+            .origin("chicken_egg")
+            .unwrap()
+            .ends_with("proteins/chicken_egg.json"));
+    }
+
+    #[test]
+    fn test_load_dir_accepts_objects_and_arrays() {
+        let dir = scratch_dir("formats");
+        fs::create_dir(dir.join("proteins")).unwrap();
+        fs::write(dir.join("proteins/egg.json"), sample_entry()).unwrap();
+        let array = sample_json().replace("chicken_egg", "duck_egg");
+        fs::write(dir.join("proteins/more.json"), array).unwrap();
+        fs::write(dir.join("README.md"), "not data").unwrap();
+
+        let mut client = SidClient::new();
+        assert_eq!(client.load_dir(&dir).unwrap(), 2);
+        assert!(client.get("duck_egg").is_some());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_dir_reports_errors_per_file() {
+        let dir = scratch_dir("errors");
+        fs::create_dir(dir.join("proteins")).unwrap();
+        fs::create_dir(dir.join("fats")).unwrap();
+        fs::write(dir.join("proteins/a.json"), sample_entry()).unwrap();
+        fs::write(dir.join("proteins/b.json"), sample_entry()).unwrap();
+        fs::write(
+            dir.join("fats/egg.json"),
+            sample_entry().replace("chicken_egg", "x_egg"),
+        )
+        .unwrap();
+        fs::write(dir.join("proteins/broken.json"), "{ \"id\": ").unwrap();
+
+        let mut client = SidClient::new();
+        let errors = client.load_dir(&dir).unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert!(errors.iter().any(|e| matches!(
+            e,
+            LoadError::DuplicateId { id, first, .. } if id == "chicken_egg" && first.ends_with("a.json")
+        )));
+        assert!(errors.iter().any(|e| matches!(
+            e,
+            LoadError::CategoryMismatch { directory, .. } if directory == "fats"
+        )));
+        let parse = errors
+            .iter()
+            .find(|e| matches!(e, LoadError::Parse { .. }))
+            .unwrap();
+        assert!(parse.path().ends_with("broken.json"));
+        assert!(parse.to_string().contains("broken.json"));
+        // The valid file still loads
+        assert_eq!(client.count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_search() {
         let mut client = SidClient::new();
-        client.load_json(sample_json()).unwrap();
+        client.load_json(&sample_json()).unwrap();
         let results = client.search("egg");
         assert_eq!(results.len(), 1);
     }
@@ -0,0 +1,145 @@
+//! Provenance and versioning of SID data
+//!
+//! Each entry cites its `sources` and may attribute individual fields to one
+//! of them in `provenance`, keyed by dotted path: `"composition"` covers every
+//! composition value, `"physical.smoke_point_celsius"` a single one, and the
+//! most specific key wins. `revisions` records how the entry changed.
+//!
+//! A loaded database is identified by [`SidVersion`]: the dataset version from
+//! `sid/data/VERSION` plus a fingerprint of the entries themselves, so a
+//! recipe's nutrition output can name the exact data it was computed from.
+
+use crate::{IngredientEntry, SidClient};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// One edit to an entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Revision {
+    /// Starts at 1 and increases with every edit
+    pub revision: u32,
+    /// `YYYY-MM-DD`
+    pub date: String,
+    pub summary: String,
+}
+
+impl IngredientEntry {
+    /// Latest revision number, 0 for entries without history
+    pub fn revision(&self) -> u32 {
+        self.revisions.last().map_or(0, |r| r.revision)
+    }
+
+    /// Source the value at `field` (e.g. `composition.water`) was taken from;
+    /// `None` unless a `provenance` key covers it
+    pub fn source_for(&self, field: &str) -> Option<&str> {
+        self.provenance
+            .iter()
+            .filter(|(key, _)| {
+                field == key.as_str()
+                    || field
+                        .strip_prefix(key.as_str())
+                        .is_some_and(|rest| rest.starts_with('.'))
+            })
+            .max_by_key(|(key, _)| key.len())
+            .map(|(_, source)| source.as_str())
+    }
+}
+
+/// Identifies the exact data a result was computed from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SidVersion {
+    /// Dataset release, absent for data not loaded from a versioned directory
+    pub version: Option<String>,
+    /// Hash of every entry, changes with any edit
+    pub fingerprint: String,
+}
+
+impl fmt::Display for SidVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.version {
+            Some(version) => write!(f, "{version}+{}", self.fingerprint),
+            None => write!(f, "unversioned+{}", self.fingerprint),
+        }
+    }
+}
+
+impl SidClient {
+    /// Dataset version and content fingerprint of the loaded entries
+    pub fn version(&self) -> SidVersion {
+        let mut entries: Vec<_> = self.entries().collect();
+        entries.sort_by(|a, b| a.id.cmp(&b.id));
+        // Through `Value` so that map fields serialize in sorted key order
+        let mut hash = FNV_OFFSET;
+        for entry in entries {
+            let json = serde_json::to_value(entry)
+                .map(|value| value.to_string())
+                .unwrap_or_default();
+            for byte in json.bytes().chain([b'\n']) {
+                hash = (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME);
+            }
+        }
+        SidVersion {
+            version: self.version.clone(),
+            fingerprint: format!("{hash:016x}"),
+        }
+    }
+
+    /// Set the dataset version, e.g. for entries loaded with [`SidClient::load_json`]
+    pub fn set_version(&mut self, version: impl Into<String>) {
+        self.version = Some(version.into());
+    }
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+#[cfg(test)]
+mod tests {
+    use crate::repository;
+
+    #[test]
+    fn test_field_sources() {
+        let client = repository();
+        let oil = client.get("sunflower_oil").unwrap();
+        assert_eq!(
+            oil.source_for("composition.total_fat"),
+            Some("USDA FoodData Central #171025")
+        );
+        assert_eq!(
+            oil.source_for("physical.smoke_point_celsius"),
+            Some("Engineering Toolbox - Smoke Points of Cooking Oils")
+        );
+        // The `physical.smoke_point_celsius` key does not cover `physical.smoke_point`
+        assert_eq!(oil.source_for("physical.smoke_point"), None);
+        assert_eq!(oil.source_for("physical.density_g_per_ml"), None);
+        assert!(oil.revision() >= 1);
+
+        let breast = client.get("chicken_breast").unwrap();
+        assert_eq!(
+            breast.source_for("physical.density_g_per_ml"),
+            Some("USDA FoodData Central #171077")
+        );
+    }
+
+    #[test]
+    fn test_version_tracks_content() {
+        let mut client = repository();
+        let version = client.version();
+        assert!(version.version.is_some());
+        assert_eq!(version, repository().version());
+
+        let mut egg = client.get("chicken_egg").unwrap().clone();
+        egg.composition.protein += 0.1;
+        client
+            .load_json(&serde_json::to_string(&[egg]).unwrap())
+            .unwrap();
+        let edited = client.version();
+        assert_eq!(edited.version, version.version);
+        assert_ne!(edited.fingerprint, version.fingerprint);
+        assert_eq!(
+            edited.to_string(),
+            format!("{}+{}", version.version.unwrap(), edited.fingerprint)
+        );
+    }
+}
@@ -0,0 +1,389 @@
+//! Nutrient queries over SID compositions
+//!
+//! Filters and sorts entries on `Composition` values per 100 g, e.g.
+//! proteins with `total_fat < 5` or everything sorted by `potassium`
+//! descending. Predicates parse from the same text the CLI accepts in
+//! `saffron ingredient --where`:
+//!
+//! ```text
+//! total_fat < 5
+//! minerals.potassium >= 300
+//! protein in 10..25
+//! ```
+//!
+//! Entries without a value for a field (no `ph`, mineral not listed) never
+//! satisfy a predicate on it and sort last.
+
+use crate::{Composition, IngredientCategory, IngredientEntry, SidClient};
+use saffron_ast::CmpOp;
+use std::cmp::Ordering;
+use std::fmt;
+use thiserror::Error;
+
+/// A numeric value in a composition
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Field {
+    Water,
+    Protein,
+    TotalFat,
+    SaturatedFat,
+    Carbohydrates,
+    Fiber,
+    Sugar,
+    Ph,
+    Mineral(String),
+    Vitamin(String),
+    /// A mineral or vitamin, whichever map lists it
+    Nutrient(String),
+}
+
+impl Field {
+    /// Parse `protein`, `fat`, `minerals.potassium`, `vitamin_c`, ...
+    pub fn parse(name: &str) -> Result<Self, QueryError> {
+        let name = name.trim();
+        let field = match name {
+            "water" => Field::Water,
+            "protein" => Field::Protein,
+            "total_fat" | "fat" => Field::TotalFat,
+            "saturated_fat" => Field::SaturatedFat,
+            "carbohydrates" | "carbs" => Field::Carbohydrates,
+            "fiber" => Field::Fiber,
+            "sugar" => Field::Sugar,
+            "ph" => Field::Ph,
+            _ => match name.split_once('.') {
+                Some(("minerals", key)) if is_key(key) => Field::Mineral(key.to_string()),
+                Some(("vitamins", key)) if is_key(key) => Field::Vitamin(key.to_string()),
+                None if is_key(name) => Field::Nutrient(name.to_string()),
+                _ => return Err(QueryError::InvalidField(name.to_string())),
+            },
+        };
+        Ok(field)
+    }
+
+    /// Value per 100 g (pH unitless), if the composition has one
+    pub fn value(&self, c: &Composition) -> Option<f64> {
+        match self {
+            Field::Water => Some(c.water),
+            Field::Protein => Some(c.protein),
+            Field::TotalFat => Some(c.total_fat),
+            Field::SaturatedFat => Some(c.saturated_fat),
+            Field::Carbohydrates => Some(c.carbohydrates),
+            Field::Fiber => Some(c.fiber),
+            Field::Sugar => Some(c.sugar),
+            Field::Ph => c.ph,
+            Field::Mineral(key) => c.minerals.get(key).copied(),
+            Field::Vitamin(key) => c.vitamins.get(key).copied(),
+            Field::Nutrient(key) => c.minerals.get(key).or_else(|| c.vitamins.get(key)).copied(),
+        }
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Field::Water => write!(f, "water"),
+            Field::Protein => write!(f, "protein"),
+            Field::TotalFat => write!(f, "total_fat"),
+            Field::SaturatedFat => write!(f, "saturated_fat"),
+            Field::Carbohydrates => write!(f, "carbohydrates"),
+            Field::Fiber => write!(f, "fiber"),
+            Field::Sugar => write!(f, "sugar"),
+            Field::Ph => write!(f, "ph"),
+            Field::Mineral(key) => write!(f, "minerals.{key}"),
+            Field::Vitamin(key) => write!(f, "vitamins.{key}"),
+            Field::Nutrient(key) => write!(f, "{key}"),
+        }
+    }
+}
+
+fn is_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum QueryError {
+    #[error("invalid field `{0}`")]
+    InvalidField(String),
+    #[error("invalid number `{0}`")]
+    InvalidNumber(String),
+    #[error("expected `<field> <op> <value>` or `<field> in <min>..<max>`, got `{0}`")]
+    InvalidPredicate(String),
+    #[error("invalid sort order `{0}` (expected asc or desc)")]
+    InvalidOrder(String),
+}
+
+/// A condition on one field
+#[derive(Debug, Clone, PartialEq)]
+pub enum Predicate {
+    Compare {
+        field: Field,
+        op: CmpOp,
+        value: f64,
+    },
+    /// Inclusive range
+    Between {
+        field: Field,
+        min: f64,
+        max: f64,
+    },
+}
+
+impl Predicate {
+    pub fn parse(text: &str) -> Result<Self, QueryError> {
+        const OPS: [(&str, CmpOp); 7] = [
+            ("<=", CmpOp::LessEqual),
+            (">=", CmpOp::GreaterEqual),
+            ("!=", CmpOp::NotEqual),
+            ("==", CmpOp::Equal),
+            ("<", CmpOp::LessThan),
+            (">", CmpOp::GreaterThan),
+            ("=", CmpOp::Equal),
+        ];
+        let invalid = || QueryError::InvalidPredicate(text.trim().to_string());
+
+        if let Some((field, range)) = text.split_once(" in ") {
+            let (min, max) = range.split_once("..").ok_or_else(invalid)?;
+            return Ok(Predicate::Between {
+                field: Field::parse(field)?,
+                min: parse_number(min)?,
+                max: parse_number(max)?,
+            });
+        }
+        for (symbol, op) in OPS {
+            if let Some((field, value)) = text.split_once(symbol) {
+                return Ok(Predicate::Compare {
+                    field: Field::parse(field)?,
+                    op,
+                    value: parse_number(value)?,
+                });
+            }
+        }
+        Err(invalid())
+    }
+
+    pub fn field(&self) -> &Field {
+        match self {
+            Predicate::Compare { field, .. } | Predicate::Between { field, .. } => field,
+        }
+    }
+
+    pub fn matches(&self, composition: &Composition) -> bool {
+        let Some(actual) = self.field().value(composition) else {
+            return false;
+        };
+        match *self {
+            Predicate::Compare { ref op, value, .. } => match op {
+                CmpOp::Equal => actual == value,
+                CmpOp::NotEqual => actual != value,
+                CmpOp::LessThan => actual < value,
+                CmpOp::LessEqual => actual <= value,
+                CmpOp::GreaterThan => actual > value,
+                CmpOp::GreaterEqual => actual >= value,
+            },
+            Predicate::Between { min, max, .. } => (min..=max).contains(&actual),
+        }
+    }
+}
+
+fn parse_number(text: &str) -> Result<f64, QueryError> {
+    let text = text.trim();
+    text.parse()
+        .map_err(|_| QueryError::InvalidNumber(text.to_string()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    Ascending,
+    Descending,
+}
+
+/// Field and direction to sort results by
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortKey {
+    pub field: Field,
+    pub order: Order,
+}
+
+impl SortKey {
+    /// Parse `potassium`, `potassium:asc` or `potassium:desc`
+    pub fn parse(text: &str) -> Result<Self, QueryError> {
+        let (field, order) = match text.split_once(':') {
+            Some((field, "asc")) => (field, Order::Ascending),
+            Some((field, "desc")) => (field, Order::Descending),
+            Some((_, order)) => return Err(QueryError::InvalidOrder(order.to_string())),
+            None => (text, Order::Ascending),
+        };
+        Ok(Self {
+            field: Field::parse(field)?,
+            order,
+        })
+    }
+
+    /// Order of two entries under this key; entries without the field sort last
+    pub fn compare(&self, a: &IngredientEntry, b: &IngredientEntry) -> Ordering {
+        match (
+            self.field.value(&a.composition),
+            self.field.value(&b.composition),
+        ) {
+            (Some(a), Some(b)) => match self.order {
+                Order::Ascending => a.total_cmp(&b),
+                Order::Descending => b.total_cmp(&a),
+            },
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+/// Filters, sort order and limit over SID entries
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NutrientQuery {
+    pub predicates: Vec<Predicate>,
+    pub category: Option<IngredientCategory>,
+    pub sort: Option<SortKey>,
+    pub limit: Option<usize>,
+}
+
+impl NutrientQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn filter(mut self, predicate: Predicate) -> Self {
+        self.predicates.push(predicate);
+        self
+    }
+
+    pub fn category(mut self, category: IngredientCategory) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    pub fn sort_by(mut self, field: Field, order: Order) -> Self {
+        self.sort = Some(SortKey { field, order });
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn matches(&self, entry: &IngredientEntry) -> bool {
+        self.category.is_none_or(|c| entry.category == c)
+            && self
+                .predicates
+                .iter()
+                .all(|p| p.matches(&entry.composition))
+    }
+}
+
+impl SidClient {
+    /// Entries matching `query`, in its sort order (by ID when unsorted or tied)
+    pub fn query(&self, query: &NutrientQuery) -> Vec<&IngredientEntry> {
+        let mut results: Vec<_> = self.entries().filter(|e| query.matches(e)).collect();
+        results.sort_by(|a, b| a.id.cmp(&b.id));
+        if let Some(key) = &query.sort {
+            results.sort_by(|a, b| key.compare(a, b));
+        }
+        if let Some(limit) = query.limit {
+            results.truncate(limit);
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository;
+
+    fn ids<'a>(entries: &[&'a IngredientEntry]) -> Vec<&'a str> {
+        entries.iter().map(|e| e.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_parse_predicates() {
+        assert_eq!(
+            Predicate::parse("fat < 5").unwrap(),
+            Predicate::Compare {
+                field: Field::TotalFat,
+                op: CmpOp::LessThan,
+                value: 5.0
+            }
+        );
+        assert_eq!(
+            Predicate::parse("minerals.potassium>=300").unwrap(),
+            Predicate::Compare {
+                field: Field::Mineral("potassium".into()),
+                op: CmpOp::GreaterEqual,
+                value: 300.0
+            }
+        );
+        assert_eq!(
+            Predicate::parse("protein in 10..25").unwrap(),
+            Predicate::Between {
+                field: Field::Protein,
+                min: 10.0,
+                max: 25.0
+            }
+        );
+        assert_eq!(
+            Predicate::parse("protein ~ 3"),
+            Err(QueryError::InvalidPredicate("protein ~ 3".into()))
+        );
+        assert_eq!(
+            Predicate::parse("Protein > 3"),
+            Err(QueryError::InvalidField("Protein".into()))
+        );
+        assert_eq!(
+            Predicate::parse("fat < five"),
+            Err(QueryError::InvalidNumber("five".into()))
+        );
+    }
+
+    #[test]
+    fn test_lean_proteins() {
+        let client = repository();
+        let query = NutrientQuery::new()
+            .category(IngredientCategory::Protein)
+            .filter(Predicate::parse("total_fat < 5").unwrap());
+        assert_eq!(ids(&client.query(&query)), ["chicken_breast"]);
+    }
+
+    #[test]
+    fn test_sort_by_mineral() {
+        let client = repository();
+        let query = NutrientQuery::new()
+            .sort_by(Field::Nutrient("potassium".into()), Order::Descending)
+            .limit(2);
+        assert_eq!(
+            ids(&client.query(&query)),
+            ["chicken_breast", "chicken_egg"]
+        );
+
+        // Entries without a pH never match a pH predicate
+        let acidic = NutrientQuery::new().filter(Predicate::parse("ph < 14").unwrap());
+        assert!(client
+            .query(&acidic)
+            .iter()
+            .all(|e| e.composition.ph.is_some()));
+    }
+
+    #[test]
+    fn test_sort_key() {
+        assert_eq!(
+            SortKey::parse("vitamins.vitamin_d:desc").unwrap(),
+            SortKey {
+                field: Field::Vitamin("vitamin_d".into()),
+                order: Order::Descending
+            }
+        );
+        assert_eq!(SortKey::parse("water").unwrap().order, Order::Ascending);
+        assert!(SortKey::parse("water:up").is_err());
+    }
+}
@@ -0,0 +1,238 @@
+//! Resolution of recipe types to SID entries
+//!
+//! Recipes name ingredients by type (`SunflowerOil(volume: 50.ml)`,
+//! `Egg(type: .Chicken)`) while the SID is keyed by ID. Entries declare the
+//! types they answer to in `aliases`. For a given type name, every parameter
+//! that appears in any of its aliases is a discriminator, and an alias matches
+//! when it agrees with the recipe on all of them: `{ "type": "Egg" }` is the
+//! entry for a bare `Egg` but not for `Egg(type: .Duck)`. Other parameters
+//! such as `volume:` are ignored.
+
+use crate::{IngredientEntry, SidClient};
+use saffron_ast::{Expr, Param, TypeRef};
+use std::collections::{BTreeMap, BTreeSet};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ResolveError {
+    #[error("no SID entry is declared for type `{0}`")]
+    UnknownType(String),
+    #[error("no SID entry matches `{type_name}({params})`")]
+    NoMatch { type_name: String, params: String },
+    #[error("`{type_name}` resolves to more than one SID entry: {}", candidates.join(", "))]
+    Ambiguous {
+        type_name: String,
+        candidates: Vec<String>,
+    },
+}
+
+/// The variant name a discriminating parameter carries: `.Chicken`,
+/// `EggType.Chicken` or `"Chicken"`
+pub fn discriminator_value(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::EnumVariant { variant, .. } => Some(variant),
+        Expr::FieldAccess { field, .. } => Some(field),
+        Expr::StringLiteral { value, .. } => Some(value),
+        _ => None,
+    }
+}
+
+impl SidClient {
+    /// Resolve a recipe ingredient such as `Egg(type: .Chicken)` to its SID entry
+    pub fn resolve(
+        &self,
+        type_ref: &TypeRef,
+        params: &[Param],
+    ) -> Result<&IngredientEntry, ResolveError> {
+        let params: BTreeMap<String, String> = params
+            .iter()
+            .filter_map(|p| Some((p.name.clone(), discriminator_value(&p.value)?.to_string())))
+            .collect();
+        self.resolve_name(&type_ref.name, &params)
+    }
+
+    /// Resolve a type name and its parameter variants to an SID entry
+    pub fn resolve_name(
+        &self,
+        type_name: &str,
+        params: &BTreeMap<String, String>,
+    ) -> Result<&IngredientEntry, ResolveError> {
+        let declared: Vec<_> = self
+            .entries()
+            .flat_map(|entry| entry.aliases.iter().map(move |alias| (entry, alias)))
+            .filter(|(_, alias)| alias.type_name == type_name)
+            .collect();
+        if declared.is_empty() {
+            return Err(ResolveError::UnknownType(type_name.to_string()));
+        }
+
+        let discriminators: BTreeSet<&str> = declared
+            .iter()
+            .flat_map(|(_, alias)| alias.params.keys().map(String::as_str))
+            .collect();
+        let mut matches: Vec<&IngredientEntry> = declared
+            .iter()
+            .filter(|(_, alias)| {
+                discriminators
+                    .iter()
+                    .all(|&key| alias.params.get(key) == params.get(key))
+            })
+            .map(|&(entry, _)| entry)
+            .collect();
+        matches.sort_by(|a, b| a.id.cmp(&b.id));
+        matches.dedup_by(|a, b| a.id == b.id);
+
+        match matches.as_slice() {
+            [entry] => Ok(entry),
+            [] => Err(ResolveError::NoMatch {
+                type_name: type_name.to_string(),
+                params: discriminators
+                    .iter()
+                    .filter_map(|&key| Some(format!("{key}: .{}", params.get(key)?)))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            }),
+            _ => Err(ResolveError::Ambiguous {
+                type_name: type_name.to_string(),
+                candidates: matches.iter().map(|e| e.id.clone()).collect(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{json_array, repository, TestEntry};
+    use saffron_ast::{Span, Unit};
+
+    fn span() -> Span {
+        Span {
+            file: "test.saffron".into(),
+            start_line: 1,
+            start_col: 1,
+            end_line: 1,
+            end_col: 1,
+            byte_offset: 0,
+            byte_length: 0,
+        }
+    }
+
+    fn type_ref(name: &str) -> TypeRef {
+        TypeRef {
+            name: name.into(),
+            generics: vec![],
+            span: span(),
+        }
+    }
+
+    fn variant(name: &str, variant: &str) -> Param {
+        Param {
+            name: name.into(),
+            value: Expr::EnumVariant {
+                variant: variant.into(),
+                span: span(),
+            },
+            span: span(),
+        }
+    }
+
+    fn beef(id: &str, cut: &str) -> TestEntry {
+        TestEntry::new(id, "protein")
+            .with("composition.water", 70.0)
+            .with("composition.protein", 20.0)
+            .with("composition.total_fat", 8.0)
+            .with("composition.saturated_fat", 3.0)
+            .with(
+                "aliases",
+                serde_json::json!([{ "type": "Beef", "params": { "cut": cut } }]),
+            )
+    }
+
+    #[test]
+    fn test_resolve_repository_types() {
+        let client = repository();
+        let volume = Param {
+            name: "volume".into(),
+            value: Expr::UnitLiteral {
+                value: 50.0,
+                unit: Unit::Milliliters,
+                span: span(),
+            },
+            span: span(),
+        };
+        let oil = client
+            .resolve(&type_ref("SunflowerOil"), &[volume])
+            .unwrap();
+        assert_eq!(oil.id, "sunflower_oil");
+
+        let egg = client
+            .resolve(&type_ref("Egg"), &[variant("type", "Chicken")])
+            .unwrap();
+        assert_eq!(egg.id, "chicken_egg");
+        assert_eq!(
+            client.resolve(&type_ref("Egg"), &[]).unwrap().id,
+            "chicken_egg"
+        );
+    }
+
+    #[test]
+    fn test_discriminators_must_agree() {
+        let client = repository();
+        let err = client
+            .resolve(&type_ref("Egg"), &[variant("type", "Duck")])
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ResolveError::NoMatch {
+                type_name: "Egg".into(),
+                params: "type: .Duck".into(),
+            }
+        );
+        assert_eq!(err.to_string(), "no SID entry matches `Egg(type: .Duck)`");
+        assert_eq!(
+            client.resolve(&type_ref("Saffron"), &[]).unwrap_err(),
+            ResolveError::UnknownType("Saffron".into())
+        );
+    }
+
+    #[test]
+    fn test_cut_selects_entry() {
+        let mut client = SidClient::new();
+        let json = json_array(&[
+            beef("beef_ribeye", "Ribeye"),
+            beef("beef_brisket", "Brisket"),
+        ]);
+        client.load_json(&json).unwrap();
+
+        let ribeye = client
+            .resolve(&type_ref("Beef"), &[variant("cut", "Ribeye")])
+            .unwrap();
+        assert_eq!(ribeye.id, "beef_ribeye");
+        // No default cut is declared
+        assert!(matches!(
+            client.resolve(&type_ref("Beef"), &[]),
+            Err(ResolveError::NoMatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_duplicate_aliases_are_ambiguous() {
+        let mut client = SidClient::new();
+        let json = json_array(&[
+            beef("beef_ribeye", "Ribeye"),
+            beef("ribeye_steak", "Ribeye"),
+        ]);
+        client.load_json(&json).unwrap();
+        let err = client
+            .resolve(&type_ref("Beef"), &[variant("cut", "Ribeye")])
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ResolveError::Ambiguous {
+                type_name: "Beef".into(),
+                candidates: vec!["beef_ribeye".into(), "ribeye_steak".into()],
+            }
+        );
+    }
+}
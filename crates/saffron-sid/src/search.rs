@@ -0,0 +1,397 @@
+//! Fuzzy, multilingual ingredient search
+//!
+//! Every localized name and the ID of each entry is normalized (lowercase,
+//! Latin diacritics folded) and split into padded character trigrams, which
+//! works for CJK names as well as alphabetic ones. A query only scores the
+//! entries that share at least one trigram with it. Exact, prefix and
+//! whole-word matches rank above substrings, which rank above fuzzy matches;
+//! fuzzy matches must be within a small edit distance or share enough
+//! trigrams, so a typo such as `chiken` still finds chicken.
+
+use crate::IngredientEntry;
+use saffron_ast::{Allergen, IngredientCategory};
+use std::collections::{HashMap, HashSet};
+
+/// Language a name was matched in; `Id` for the SID identifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    En,
+    Es,
+    Fr,
+    Zh,
+    Ja,
+    Id,
+}
+
+/// Search text and filters
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchQuery {
+    /// Name in any language; empty to list everything that passes the filters
+    pub text: String,
+    pub category: Option<IngredientCategory>,
+    pub subcategory: Option<String>,
+    /// Only entries free of all of these allergens
+    pub free_from: Vec<Allergen>,
+    pub limit: usize,
+}
+
+impl SearchQuery {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            category: None,
+            subcategory: None,
+            free_from: Vec::new(),
+            limit: 20,
+        }
+    }
+
+    pub fn category(mut self, category: IngredientCategory) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    pub fn subcategory(mut self, subcategory: impl Into<String>) -> Self {
+        self.subcategory = Some(subcategory.into());
+        self
+    }
+
+    pub fn free_from(mut self, allergen: Allergen) -> Self {
+        self.free_from.push(allergen);
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+}
+
+/// A ranked search result
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub id: String,
+    /// Relevance, 0.0–1.0
+    pub score: f64,
+    /// The name that matched, as written in the entry
+    pub matched: String,
+    pub language: Language,
+}
+
+#[derive(Debug, Clone)]
+struct Term {
+    language: Language,
+    original: String,
+    normalized: String,
+}
+
+#[derive(Debug, Clone)]
+struct Document {
+    id: String,
+    category: IngredientCategory,
+    subcategory: Option<String>,
+    allergens: Vec<Allergen>,
+    terms: Vec<Term>,
+}
+
+/// Prebuilt trigram index over entry names
+#[derive(Debug, Clone, Default)]
+pub struct SearchIndex {
+    documents: Vec<Document>,
+    /// Trigram → documents containing it
+    postings: HashMap<String, Vec<usize>>,
+}
+
+/// Minimum score for a fuzzy match to be reported
+const MIN_FUZZY_SCORE: f64 = 0.3;
+
+impl SearchIndex {
+    pub fn build<'a>(entries: impl IntoIterator<Item = &'a IngredientEntry>) -> Self {
+        let mut entries: Vec<_> = entries.into_iter().collect();
+        entries.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let mut index = Self::default();
+        for entry in entries {
+            let name = &entry.name;
+            let names = [
+                (Language::En, Some(&name.en)),
+                (Language::Es, name.es.as_ref()),
+                (Language::Fr, name.fr.as_ref()),
+                (Language::Zh, name.zh.as_ref()),
+                (Language::Ja, name.ja.as_ref()),
+                (Language::Id, Some(&entry.id)),
+            ];
+            let terms: Vec<Term> = names
+                .into_iter()
+                .filter_map(|(language, text)| {
+                    let text = text?;
+                    Some(Term {
+                        language,
+                        original: text.clone(),
+                        normalized: normalize(text),
+                    })
+                })
+                .collect();
+
+            let doc = index.documents.len();
+            let grams: HashSet<String> = terms
+                .iter()
+                .flat_map(|term| trigrams(&term.normalized))
+                .collect();
+            for gram in grams {
+                index.postings.entry(gram).or_default().push(doc);
+            }
+            index.documents.push(Document {
+                id: entry.id.clone(),
+                category: entry.category,
+                subcategory: entry.subcategory.clone(),
+                allergens: entry.allergens.clone(),
+                terms,
+            });
+        }
+        index
+    }
+
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Matching entries, best first (ties by ID)
+    pub fn search(&self, query: &SearchQuery) -> Vec<SearchHit> {
+        let text = normalize(&query.text);
+        let candidates: Vec<usize> = if text.is_empty() {
+            (0..self.documents.len()).collect()
+        } else {
+            let mut docs: Vec<usize> = trigrams(&text)
+                .iter()
+                .filter_map(|gram| self.postings.get(gram))
+                .flatten()
+                .copied()
+                .collect();
+            docs.sort_unstable();
+            docs.dedup();
+            docs
+        };
+
+        let mut hits: Vec<SearchHit> = candidates
+            .into_iter()
+            .map(|doc| &self.documents[doc])
+            .filter(|doc| passes_filters(doc, query))
+            .filter_map(|doc| {
+                if text.is_empty() {
+                    let en = &doc.terms[0];
+                    return Some(SearchHit {
+                        id: doc.id.clone(),
+                        score: 1.0,
+                        matched: en.original.clone(),
+                        language: en.language,
+                    });
+                }
+                // Reversed so that ties go to the earlier language, English first
+                let (score, term) = doc
+                    .terms
+                    .iter()
+                    .rev()
+                    .map(|term| (score(&text, &term.normalized), term))
+                    .max_by(|a, b| a.0.total_cmp(&b.0))?;
+                (score >= MIN_FUZZY_SCORE).then(|| SearchHit {
+                    id: doc.id.clone(),
+                    score,
+                    matched: term.original.clone(),
+                    language: term.language,
+                })
+            })
+            .collect();
+
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.id.cmp(&b.id)));
+        hits.truncate(query.limit);
+        hits
+    }
+}
+
+fn passes_filters(doc: &Document, query: &SearchQuery) -> bool {
+    query.category.is_none_or(|c| doc.category == c)
+        && query
+            .subcategory
+            .as_ref()
+            .is_none_or(|s| doc.subcategory.as_ref() == Some(s))
+        && !query.free_from.iter().any(|a| doc.allergens.contains(a))
+}
+
+/// Relevance of `term` for `query`, both normalized
+fn score(query: &str, term: &str) -> f64 {
+    if term == query {
+        return 1.0;
+    }
+    if term.starts_with(query) {
+        return 0.9;
+    }
+    let words: Vec<&str> = term.split(' ').collect();
+    if words.contains(&query) {
+        return 0.85;
+    }
+    if words.iter().any(|w| w.starts_with(query)) {
+        return 0.8;
+    }
+    if term.contains(query) {
+        return 0.7;
+    }
+
+    // Fuzzy: whole name against whole query, and each word against each query word
+    let mut best = similarity(query, term);
+    let query_words: Vec<&str> = query.split(' ').collect();
+    let word_scores: f64 = query_words
+        .iter()
+        .map(|q| words.iter().map(|w| similarity(q, w)).fold(0.0, f64::max))
+        .sum();
+    best = best.max(word_scores / query_words.len() as f64);
+    0.6 * best
+}
+
+/// 0.0–1.0 similarity tolerating typos: edit distance within budget, else trigram overlap
+fn similarity(a: &str, b: &str) -> f64 {
+    let len = a.chars().count().max(b.chars().count());
+    let budget = if len <= 4 { 1 } else { 2 };
+    let distance = levenshtein(a, b);
+    let edit = if distance <= budget {
+        1.0 - distance as f64 / len as f64
+    } else {
+        0.0
+    };
+    let (ga, gb) = (trigrams(a), trigrams(b));
+    let shared = ga.intersection(&gb).count();
+    let dice = 2.0 * shared as f64 / (ga.len() + gb.len()).max(1) as f64;
+    edit.max(dice)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = (above + 1)
+                .min(row[j] + 1)
+                .min(diagonal + usize::from(ca != cb));
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// Padded character trigrams, so one- and two-character names still index
+fn trigrams(text: &str) -> HashSet<String> {
+    let chars: Vec<char> = format!("  {text} ").chars().collect();
+    chars.windows(3).map(|w| w.iter().collect()).collect()
+}
+
+/// Lowercase, fold Latin diacritics and treat `_`/`-` as spaces
+pub fn normalize(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.trim().chars().flat_map(char::to_lowercase) {
+        match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => out.push('a'),
+            'ç' => out.push('c'),
+            'è' | 'é' | 'ê' | 'ë' => out.push('e'),
+            'ì' | 'í' | 'î' | 'ï' => out.push('i'),
+            'ñ' => out.push('n'),
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' => out.push('o'),
+            'ù' | 'ú' | 'û' | 'ü' => out.push('u'),
+            'ý' | 'ÿ' => out.push('y'),
+            'œ' => out.push_str("oe"),
+            'æ' => out.push_str("ae"),
+            'ß' => out.push_str("ss"),
+            '_' | '-' => out.push(' '),
+            c if c.is_whitespace() => {
+                if !out.ends_with(' ') {
+                    out.push(' ');
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository;
+
+    fn ids(hits: &[SearchHit]) -> Vec<&str> {
+        hits.iter().map(|h| h.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("  Œuf de  Poule "), "oeuf de poule");
+        assert_eq!(normalize("chicken_egg"), "chicken egg");
+        assert_eq!(normalize("Piñón"), "pinon");
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("chiken", "chicken"), 1);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("鸡蛋", "鸡肉"), 1);
+    }
+
+    #[test]
+    fn test_search_in_any_language() {
+        let client = repository();
+        for (query, id, language) in [
+            ("huevo", "chicken_egg", Language::Es),
+            ("oeuf", "chicken_egg", Language::Fr),
+            ("鸡蛋", "chicken_egg", Language::Zh),
+            ("ひまわり", "sunflower_oil", Language::Ja),
+            ("table salt", "table_salt", Language::En),
+        ] {
+            let hits = client.search_with(&SearchQuery::new(query));
+            assert_eq!(hits[0].id, id, "{query}");
+            assert_eq!(hits[0].language, language, "{query}");
+        }
+        assert_eq!(
+            client.search_with(&SearchQuery::new("Œuf"))[0].matched,
+            "Oeuf de poule"
+        );
+    }
+
+    #[test]
+    fn test_typos_and_ranking() {
+        let client = repository();
+        let hits = client.search_with(&SearchQuery::new("chiken"));
+        assert_eq!(ids(&hits), ["chicken_breast", "chicken_egg"]);
+        assert!(hits[0].score < 0.7);
+
+        let hits = client.search_with(&SearchQuery::new("sunflwer oil"));
+        assert_eq!(hits[0].id, "sunflower_oil");
+
+        let hits = client.search_with(&SearchQuery::new("chicken egg"));
+        assert_eq!(hits[0].id, "chicken_egg");
+        assert!(hits[0].score > hits[1].score);
+
+        assert!(client.search_with(&SearchQuery::new("saffron")).is_empty());
+    }
+
+    #[test]
+    fn test_filters() {
+        let client = repository();
+        let proteins = SearchQuery::new("").category(IngredientCategory::Protein);
+        assert_eq!(
+            ids(&client.search_with(&proteins)),
+            ["chicken_breast", "chicken_egg"]
+        );
+        let egg_free = SearchQuery::new("chicken").free_from(Allergen::Eggs);
+        assert_eq!(ids(&client.search_with(&egg_free)), ["chicken_breast"]);
+        let eggs = SearchQuery::new("chicken").subcategory("egg");
+        assert_eq!(ids(&client.search_with(&eggs)), ["chicken_egg"]);
+        let one = SearchQuery::new("chicken").limit(1);
+        assert_eq!(client.search_with(&one).len(), 1);
+    }
+}
@@ -0,0 +1,482 @@
+//! Ingredient substitution
+//!
+//! Ranks replacement candidates for an ingredient. The score combines the
+//! curated `substitutes` lists (in either direction) with how closely the
+//! candidate's macronutrient composition and physical properties match, and
+//! every candidate comes with the quantity conversion that keeps the
+//! dominant component (water, protein, fat or carbohydrate) the same.
+//!
+//! Constraints mirror the AI hint `///ai: substitution egg->flax_egg when=vegan`:
+//! allergen-free, diet, and frying-relevant smoke point. The hint's target is
+//! ranked as if it were declared.
+
+use crate::{Allergen, Diet, IngredientEntry, SidClient};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// Weight of the curated `substitutes` lists in the final score
+const DECLARED_WEIGHT: f64 = 0.4;
+
+/// Weight of composition and physical similarity
+const SIMILARITY_WEIGHT: f64 = 0.5;
+
+/// Bonus for staying within the same category
+const CATEGORY_WEIGHT: f64 = 0.1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SubstituteError {
+    #[error("unknown ingredient `{0}`")]
+    UnknownIngredient(String),
+    #[error("`{0}` has no smoke point to compare against")]
+    NoSmokePoint(String),
+}
+
+/// The ingredient to replace and the constraints on its replacement
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubstitutionRequest {
+    pub id: String,
+    pub free_from: Vec<Allergen>,
+    pub diet: Option<Diet>,
+    /// Maximum smoke point difference, °C
+    pub smoke_point_tolerance: Option<f64>,
+    /// IDs ranked as declared substitutes, e.g. the target of an AI hint
+    pub preferred: Vec<String>,
+    pub limit: usize,
+}
+
+impl SubstitutionRequest {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            free_from: Vec::new(),
+            diet: None,
+            smoke_point_tolerance: None,
+            preferred: Vec::new(),
+            limit: 10,
+        }
+    }
+
+    pub fn free_from(mut self, allergen: Allergen) -> Self {
+        self.free_from.push(allergen);
+        self
+    }
+
+    pub fn diet(mut self, diet: Diet) -> Self {
+        self.diet = Some(diet);
+        self
+    }
+
+    pub fn similar_smoke_point(mut self, tolerance_c: f64) -> Self {
+        self.smoke_point_tolerance = Some(tolerance_c);
+        self
+    }
+
+    pub fn prefer(mut self, id: impl Into<String>) -> Self {
+        self.preferred.push(id.into());
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+}
+
+/// Quantity of the substitute per unit of the original
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Conversion {
+    /// Grams of substitute per gram of original
+    pub mass_ratio: f64,
+    /// Millilitres of substitute per millilitre of original, when both densities are known
+    pub volume_ratio: Option<f64>,
+}
+
+impl Conversion {
+    pub fn mass_g(&self, original_g: f64) -> f64 {
+        original_g * self.mass_ratio
+    }
+
+    pub fn volume_ml(&self, original_ml: f64) -> Option<f64> {
+        self.volume_ratio.map(|ratio| original_ml * ratio)
+    }
+}
+
+/// A ranked replacement
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub id: String,
+    /// Overall rank, 0.0–1.0
+    pub score: f64,
+    /// Listed as a substitute by either entry
+    pub declared: bool,
+    /// Composition and physical similarity, 0.0–1.0
+    pub similarity: f64,
+    pub conversion: Conversion,
+}
+
+/// Parsed `///ai: substitution <from>-><to> when=<condition>[,<condition>]` hint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubstitutionHint {
+    pub from: String,
+    pub to: String,
+    /// `vegan`, `vegetarian` or `<allergen>_free`
+    pub when: Vec<String>,
+}
+
+impl SubstitutionHint {
+    /// Parse the content of an AI hint; `None` if it is not a substitution
+    pub fn parse(hint: &str) -> Option<Self> {
+        let mut words = hint.split_whitespace();
+        if words.next()? != "substitution" {
+            return None;
+        }
+        let (from, to) = words.next()?.split_once("->")?;
+        let when = words
+            .filter_map(|w| w.strip_prefix("when="))
+            .flat_map(|w| w.split(','))
+            .map(str::to_string)
+            .collect();
+        Some(Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            when,
+        })
+    }
+
+    /// Apply the hint to a request for its `from` ingredient: the `when=`
+    /// conditions become constraints (unknown ones are ignored) and `to` is
+    /// preferred. Names resolve as in [`SidClient::resolve_hint_name`]; a
+    /// hint whose `from` is a different ingredient leaves the request as is.
+    pub fn constrain(
+        &self,
+        sid: &SidClient,
+        mut request: SubstitutionRequest,
+    ) -> SubstitutionRequest {
+        if sid
+            .resolve_hint_name(&self.from)
+            .is_none_or(|from| from.id != request.id)
+        {
+            return request;
+        }
+        if let Some(to) = sid.resolve_hint_name(&self.to) {
+            request.preferred.push(to.id.clone());
+        }
+        for condition in &self.when {
+            match condition.as_str() {
+                "vegan" => request.diet = Some(Diet::Vegan),
+                "vegetarian" => request.diet = Some(Diet::Vegetarian),
+                other => {
+                    let allergen = other
+                        .strip_suffix("_free")
+                        .and_then(|name| serde_json::from_value(name.into()).ok());
+                    if let Some(allergen) = allergen {
+                        request.free_from.push(allergen);
+                    }
+                }
+            }
+        }
+        request
+    }
+}
+
+impl SidClient {
+    /// Entry named in an AI hint: an SID ID, a type name, or a type name in
+    /// snake case (`chicken_breast` for `ChickenBreast`)
+    pub fn resolve_hint_name(&self, name: &str) -> Option<&IngredientEntry> {
+        let no_params = BTreeMap::new();
+        self.get(name)
+            .or_else(|| self.resolve_name(name, &no_params).ok())
+            .or_else(|| self.resolve_name(&pascal_case(name), &no_params).ok())
+    }
+
+    /// Ranked substitutes for `request.id`, best first
+    pub fn substitutes(
+        &self,
+        request: &SubstitutionRequest,
+    ) -> Result<Vec<Candidate>, SubstituteError> {
+        let original = self
+            .get(&request.id)
+            .ok_or_else(|| SubstituteError::UnknownIngredient(request.id.clone()))?;
+        let original_smoke = original.physical.smoke_point_celsius;
+        if request.smoke_point_tolerance.is_some() && original_smoke.is_none() {
+            return Err(SubstituteError::NoSmokePoint(original.id.clone()));
+        }
+
+        let mut candidates: Vec<Candidate> = self
+            .entries()
+            .filter(|c| c.id != original.id)
+            .filter(|c| !request.free_from.iter().any(|a| c.allergens.contains(a)))
+            .filter(|c| request.diet.is_none_or(|diet| suits(c, diet)))
+            .filter(|c| match (request.smoke_point_tolerance, original_smoke) {
+                (Some(tolerance), Some(smoke)) => c
+                    .physical
+                    .smoke_point_celsius
+                    .is_some_and(|s| (s - smoke).abs() <= tolerance),
+                _ => true,
+            })
+            .map(|c| {
+                let listed =
+                    original.substitutes.contains(&c.id) || request.preferred.contains(&c.id);
+                let declared = listed || c.substitutes.contains(&original.id);
+                let declared_score = if listed {
+                    1.0
+                } else if declared {
+                    0.6
+                } else {
+                    0.0
+                };
+                let similarity = similarity(original, c);
+                let same_category = if c.category == original.category {
+                    1.0
+                } else {
+                    0.0
+                };
+                Candidate {
+                    id: c.id.clone(),
+                    score: DECLARED_WEIGHT * declared_score
+                        + SIMILARITY_WEIGHT * similarity
+                        + CATEGORY_WEIGHT * same_category,
+                    declared,
+                    similarity,
+                    conversion: conversion(original, c),
+                }
+            })
+            .collect();
+
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.id.cmp(&b.id)));
+        candidates.truncate(request.limit);
+        Ok(candidates)
+    }
+}
+
+/// `flax_egg` -> `FlaxEgg`
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase())
+                .into_iter()
+                .chain(chars)
+        })
+        .collect()
+}
+
+fn suits(entry: &IngredientEntry, diet: Diet) -> bool {
+    match diet {
+        Diet::Vegan => entry.diets.contains(&Diet::Vegan),
+        // Vegan food is vegetarian whether or not the data says so
+        Diet::Vegetarian => entry
+            .diets
+            .iter()
+            .any(|d| matches!(d, Diet::Vegan | Diet::Vegetarian)),
+    }
+}
+
+/// Water, protein, fat and carbohydrates per 100 g
+fn macros(entry: &IngredientEntry) -> [f64; 4] {
+    let c = &entry.composition;
+    [c.water, c.protein, c.total_fat, c.carbohydrates]
+}
+
+/// 0.0–1.0: composition distance blended with physical property ratios
+fn similarity(a: &IngredientEntry, b: &IngredientEntry) -> f64 {
+    let distance: f64 = macros(a)
+        .iter()
+        .zip(macros(b))
+        .map(|(x, y)| (x - y).abs())
+        .sum();
+    // Two entries with nothing in common differ by at most 200 g per 100 g
+    let composition = 1.0 - (distance / 200.0).min(1.0);
+
+    let (pa, pb) = (&a.physical, &b.physical);
+    let ratios: Vec<f64> = [
+        (pa.density_g_per_ml, pb.density_g_per_ml),
+        (pa.specific_heat_j_per_g_k, pb.specific_heat_j_per_g_k),
+        (pa.smoke_point_celsius, pb.smoke_point_celsius),
+    ]
+    .into_iter()
+    .filter_map(|pair| match pair {
+        (Some(x), Some(y)) if x > 0.0 && y > 0.0 => Some(x.min(y) / x.max(y)),
+        _ => None,
+    })
+    .collect();
+    if ratios.is_empty() {
+        composition
+    } else {
+        let physical = ratios.iter().sum::<f64>() / ratios.len() as f64;
+        0.7 * composition + 0.3 * physical
+    }
+}
+
+/// Keep the original's dominant component constant
+fn conversion(original: &IngredientEntry, substitute: &IngredientEntry) -> Conversion {
+    let (from, to) = (macros(original), macros(substitute));
+    let dominant = (0..4)
+        .max_by(|&i, &j| from[i].total_cmp(&from[j]))
+        .unwrap_or(0);
+    let mass_ratio = if to[dominant] > 0.0 {
+        (from[dominant] / to[dominant]).clamp(0.25, 4.0)
+    } else {
+        1.0
+    };
+    let volume_ratio = match (
+        original.physical.density_g_per_ml,
+        substitute.physical.density_g_per_ml,
+    ) {
+        (Some(from), Some(to)) if to > 0.0 => Some(mass_ratio * from / to),
+        _ => None,
+    };
+    Conversion {
+        mass_ratio,
+        volume_ratio,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{json_array, repository, TestEntry};
+
+    fn fat(id: &str, fat: f64, water: f64, smoke: f64) -> TestEntry {
+        TestEntry::new(id, "fat")
+            .with("composition.water", water)
+            .with("composition.total_fat", fat)
+            .with("composition.saturated_fat", 10.0)
+            .with("physical.density_g_per_ml", 0.92)
+            .with("physical.smoke_point_celsius", smoke)
+    }
+
+    fn fats() -> SidClient {
+        let json = json_array(&[
+            fat("sunflower_oil", 100.0, 0.0, 232.0)
+                .with("diets", vec!["vegan"])
+                .with("substitutes", vec!["canola_oil"]),
+            fat("canola_oil", 100.0, 0.0, 204.0).with("diets", vec!["vegan"]),
+            fat("peanut_oil", 100.0, 0.0, 230.0).with("allergens", vec!["peanuts"]),
+            fat("butter", 81.0, 16.0, 150.0)
+                .with("allergens", vec!["milk"])
+                .with("diets", vec!["vegetarian"]),
+        ]);
+        let mut client = SidClient::new();
+        client.load_json(&json).unwrap();
+        client
+    }
+
+    fn ids(candidates: &[Candidate]) -> Vec<&str> {
+        candidates.iter().map(|c| c.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_declared_substitutes_rank_first() {
+        let client = fats();
+        let candidates = client
+            .substitutes(&SubstitutionRequest::new("sunflower_oil"))
+            .unwrap();
+        assert_eq!(ids(&candidates), ["canola_oil", "peanut_oil", "butter"]);
+        assert!(candidates[0].declared);
+        assert!(candidates[1].similarity > candidates[2].similarity);
+    }
+
+    #[test]
+    fn test_constraints() {
+        let client = fats();
+        let frying = SubstitutionRequest::new("sunflower_oil").similar_smoke_point(10.0);
+        assert_eq!(ids(&client.substitutes(&frying).unwrap()), ["peanut_oil"]);
+
+        let vegan = SubstitutionRequest::new("butter").diet(Diet::Vegan);
+        assert_eq!(
+            ids(&client.substitutes(&vegan).unwrap()),
+            ["canola_oil", "sunflower_oil"]
+        );
+        let nut_free = SubstitutionRequest::new("butter").free_from(Allergen::Peanuts);
+        assert!(!ids(&client.substitutes(&nut_free).unwrap()).contains(&"peanut_oil"));
+
+        assert_eq!(
+            client.substitutes(&SubstitutionRequest::new("lard")),
+            Err(SubstituteError::UnknownIngredient("lard".into()))
+        );
+    }
+
+    #[test]
+    fn test_conversion_keeps_fat_constant() {
+        let client = fats();
+        let candidates = client
+            .substitutes(&SubstitutionRequest::new("sunflower_oil"))
+            .unwrap();
+        let butter = candidates.iter().find(|c| c.id == "butter").unwrap();
+        assert!((butter.conversion.mass_g(81.0) - 100.0).abs() < 1e-9);
+        let oil = &candidates[0];
+        assert_eq!(oil.conversion.mass_ratio, 1.0);
+        assert_eq!(oil.conversion.volume_ml(50.0), Some(50.0));
+    }
+
+    #[test]
+    fn test_ai_hint() {
+        let hint = SubstitutionHint::parse("substitution egg->flax_egg when=vegan").unwrap();
+        assert_eq!(hint.from, "egg");
+        assert_eq!(hint.to, "flax_egg");
+        assert_eq!(hint.when, ["vegan"]);
+        assert!(SubstitutionHint::parse("suggest_alternative=vegan").is_none());
+
+        let client = repository();
+        let hint =
+            SubstitutionHint::parse("substitution egg->x when=vegetarian,eggs_free").unwrap();
+        let request = hint.constrain(&client, SubstitutionRequest::new("chicken_egg"));
+        assert_eq!(request.diet, Some(Diet::Vegetarian));
+        assert_eq!(request.free_from, [Allergen::Eggs]);
+        assert!(request.preferred.is_empty());
+
+        // A hint about another ingredient does not apply
+        let unrelated = SubstitutionRequest::new("sunflower_oil");
+        assert_eq!(hint.constrain(&client, unrelated.clone()), unrelated);
+    }
+
+    #[test]
+    fn test_hint_target_ranks_as_declared() {
+        let client = repository();
+        let plain = client
+            .substitutes(&SubstitutionRequest::new("chicken_breast"))
+            .unwrap();
+        let before = plain.iter().find(|c| c.id == "water").unwrap();
+        assert!(!before.declared);
+
+        let hint = SubstitutionHint::parse("substitution ChickenBreast->water").unwrap();
+        let request = hint.constrain(&client, SubstitutionRequest::new("chicken_breast"));
+        assert_eq!(request.preferred, ["water"]);
+        let hinted = client.substitutes(&request).unwrap();
+        let after = hinted.iter().find(|c| c.id == "water").unwrap();
+        assert!(after.declared);
+        assert!(after.score > before.score);
+
+        assert_eq!(pascal_case("chicken_breast"), "ChickenBreast");
+        assert_eq!(
+            client.resolve_hint_name("sunflower_oil").unwrap().id,
+            "sunflower_oil"
+        );
+        assert_eq!(client.resolve_hint_name("egg").unwrap().id, "chicken_egg");
+        assert!(client.resolve_hint_name("flax_egg").is_none());
+    }
+
+    #[test]
+    fn test_repository_substitutes() {
+        let client = repository();
+        let candidates = client
+            .substitutes(&SubstitutionRequest::new("chicken_breast"))
+            .unwrap();
+        assert_eq!(candidates[0].id, "chicken_egg");
+
+        let egg_free = SubstitutionRequest::new("chicken_breast").free_from(Allergen::Eggs);
+        assert!(!ids(&client.substitutes(&egg_free).unwrap()).contains(&"chicken_egg"));
+
+        let vegan = SubstitutionRequest::new("chicken_egg").diet(Diet::Vegan);
+        for candidate in client.substitutes(&vegan).unwrap() {
+            assert!(client
+                .get(&candidate.id)
+                .unwrap()
+                .diets
+                .contains(&Diet::Vegan));
+        }
+    }
+}
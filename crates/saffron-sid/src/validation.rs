@@ -0,0 +1,515 @@
+//! Schema validation for SID entries
+//!
+//! Mirrors `sid/schema/ingredient.schema.json`: serde already enforces the
+//! shape, unknown fields and the category, allergen and month enumerations;
+//! this module checks the ranges, cross-field rules and references between
+//! entries that the type system cannot.
+
+use crate::{Alias, IngredientEntry, SidClient};
+use std::collections::hash_map::{Entry, HashMap};
+use thiserror::Error;
+
+/// What is wrong with a field
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ValidationErrorKind {
+    #[error("invalid id format (must match ^[a-z][a-z0-9_]*$)")]
+    InvalidId,
+    #[error("must not be empty")]
+    Empty,
+    #[error("must be between {min} and {max}, got {value}")]
+    OutOfRange { value: f64, min: f64, max: f64 },
+    #[error("at least one source must be cited")]
+    MissingSources,
+    #[error("water, protein, fat and carbohydrates add up to {total} g per 100 g")]
+    CompositionExceeds100 { total: f64 },
+    #[error("refers to unknown ingredient `{0}`")]
+    UnknownSubstitute(String),
+    #[error("type `{0}` must be PascalCase")]
+    InvalidAliasType(String),
+    #[error("is also declared by `{0}`")]
+    DuplicateAlias(String),
+    #[error("does not name a field")]
+    UnknownField,
+    #[error("cites `{0}`, which is not listed in sources")]
+    UnknownSource(String),
+    #[error("must be a YYYY-MM-DD date, got `{0}`")]
+    InvalidDate(String),
+    #[error("revision {revision} does not follow revision {previous}")]
+    RevisionOrder { previous: u32, revision: u32 },
+    #[error("must be one of {}, got `{value}`", .allowed.join(", "))]
+    NotOneOf {
+        value: String,
+        allowed: &'static [&'static str],
+    },
+    #[error("must be positive, got {0}")]
+    NotPositive(f64),
+}
+
+/// Protein matrices a denaturation profile can describe
+const DENATURATION_SYSTEMS: &[&str] = &["meat", "egg_white", "egg_yolk"];
+
+/// Protein fractions with known kinetics
+const PROTEIN_FRACTIONS: &[&str] = &[
+    "myosin",
+    "actin",
+    "collagen",
+    "ovalbumin",
+    "conalbumin",
+    "yolk_lipoprotein",
+];
+
+impl ValidationErrorKind {
+    /// Warnings flag gaps in the database rather than wrong data: substitutes
+    /// may name ingredients that have not been added yet
+    pub fn is_warning(&self) -> bool {
+        matches!(self, ValidationErrorKind::UnknownSubstitute(_))
+    }
+}
+
+/// A schema violation in one field of one entry
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("{id}: {field} {kind}")]
+pub struct ValidationError {
+    pub id: String,
+    /// Dotted path of the field, e.g. `composition.water`
+    pub field: String,
+    pub kind: ValidationErrorKind,
+}
+
+/// Check one entry against the schema rules
+pub fn validate(entry: &IngredientEntry) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    let mut push = |field: &str, kind| {
+        errors.push(ValidationError {
+            id: entry.id.clone(),
+            field: field.to_string(),
+            kind,
+        })
+    };
+
+    if !is_valid_id(&entry.id) {
+        push("id", ValidationErrorKind::InvalidId);
+    }
+    if entry.name.en.trim().is_empty() {
+        push("name.en", ValidationErrorKind::Empty);
+    }
+
+    let c = &entry.composition;
+    let percentages = [
+        ("composition.water", c.water),
+        ("composition.protein", c.protein),
+        ("composition.total_fat", c.total_fat),
+        ("composition.saturated_fat", c.saturated_fat),
+        ("composition.carbohydrates", c.carbohydrates),
+        ("composition.fiber", c.fiber),
+        ("composition.sugar", c.sugar),
+    ];
+    for (field, value) in percentages {
+        if let Some(kind) = out_of_range(value, 0.0, 100.0) {
+            push(field, kind);
+        }
+    }
+    if let Some(kind) = c.ph.and_then(|ph| out_of_range(ph, 0.0, 14.0)) {
+        push("composition.ph", kind);
+    }
+    // Fiber and sugar are part of carbohydrates, saturated fat part of total fat
+    let total = c.water + c.protein + c.total_fat + c.carbohydrates;
+    if total > 100.0 {
+        push(
+            "composition",
+            ValidationErrorKind::CompositionExceeds100 { total },
+        );
+    }
+
+    for (i, alias) in entry.aliases.iter().enumerate() {
+        if !is_pascal_case(&alias.type_name) {
+            push(
+                &format!("aliases[{i}]"),
+                ValidationErrorKind::InvalidAliasType(alias.type_name.clone()),
+            );
+        }
+    }
+
+    for (i, profile) in entry.denaturation.iter().enumerate() {
+        let path = format!("denaturation[{i}]");
+        if !DENATURATION_SYSTEMS.contains(&profile.system.as_str()) {
+            push(
+                &format!("{path}.system"),
+                ValidationErrorKind::NotOneOf {
+                    value: profile.system.clone(),
+                    allowed: DENATURATION_SYSTEMS,
+                },
+            );
+        }
+        if profile.fractions.is_empty() {
+            push(&format!("{path}.fractions"), ValidationErrorKind::Empty);
+        }
+        for (j, fraction) in profile.fractions.iter().enumerate() {
+            let path = format!("{path}.fractions[{j}]");
+            if !PROTEIN_FRACTIONS.contains(&fraction.protein.as_str()) {
+                push(
+                    &format!("{path}.protein"),
+                    ValidationErrorKind::NotOneOf {
+                        value: fraction.protein.clone(),
+                        allowed: PROTEIN_FRACTIONS,
+                    },
+                );
+            }
+            if let Some(kind) = out_of_range(fraction.share, 0.0, 1.0) {
+                push(&format!("{path}.share"), kind);
+            }
+            let rates = [
+                (
+                    "activation_energy_j_per_mol",
+                    fraction.activation_energy_j_per_mol,
+                ),
+                ("reference_rate_per_s", fraction.reference_rate_per_s),
+            ];
+            for (field, value) in rates {
+                if value.is_nan() || value <= 0.0 {
+                    push(
+                        &format!("{path}.{field}"),
+                        ValidationErrorKind::NotPositive(value),
+                    );
+                }
+            }
+        }
+    }
+
+    if entry.sources.iter().all(|s| s.trim().is_empty()) {
+        push("sources", ValidationErrorKind::MissingSources);
+    }
+
+    let json = serde_json::to_value(entry).unwrap_or_default();
+    for (field, source) in &entry.provenance {
+        let path = format!("provenance.{field}");
+        if field
+            .split('.')
+            .try_fold(&json, |value, key| value.get(key))
+            .is_none()
+        {
+            push(&path, ValidationErrorKind::UnknownField);
+        }
+        if !entry.sources.contains(source) {
+            push(&path, ValidationErrorKind::UnknownSource(source.clone()));
+        }
+    }
+
+    let mut previous = 0;
+    for (i, revision) in entry.revisions.iter().enumerate() {
+        if revision.revision <= previous {
+            push(
+                &format!("revisions[{i}].revision"),
+                ValidationErrorKind::RevisionOrder {
+                    previous,
+                    revision: revision.revision,
+                },
+            );
+        }
+        if !is_valid_date(&revision.date) {
+            push(
+                &format!("revisions[{i}].date"),
+                ValidationErrorKind::InvalidDate(revision.date.clone()),
+            );
+        }
+        previous = revision.revision;
+    }
+
+    errors
+}
+
+fn is_valid_id(id: &str) -> bool {
+    let mut chars = id.chars();
+    chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn is_pascal_case(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_uppercase()) && chars.all(|c| c.is_ascii_alphanumeric())
+}
+
+fn is_valid_date(date: &str) -> bool {
+    let parts: Vec<&str> = date.split('-').collect();
+    let number = |part: &str, len: usize, max: u32| {
+        part.len() == len
+            && part.bytes().all(|b| b.is_ascii_digit())
+            && part.parse::<u32>().is_ok_and(|n| n >= 1 && n <= max)
+    };
+    matches!(parts.as_slice(), [y, m, d] if number(y, 4, 9999) && number(m, 2, 12) && number(d, 2, 31))
+}
+
+fn out_of_range(value: f64, min: f64, max: f64) -> Option<ValidationErrorKind> {
+    // Written so that NaN fails too
+    if value >= min && value <= max {
+        None
+    } else {
+        Some(ValidationErrorKind::OutOfRange { value, min, max })
+    }
+}
+
+impl SidClient {
+    /// Validate every loaded entry, its substitutes and aliases, ordered by id
+    pub fn validate(&self) -> Vec<ValidationError> {
+        let mut entries: Vec<_> = self.entries().collect();
+        entries.sort_by(|a, b| a.id.cmp(&b.id));
+        let mut errors = Vec::new();
+        let mut aliases: HashMap<&Alias, &str> = HashMap::new();
+        for entry in entries {
+            errors.extend(validate(entry));
+            for (i, alias) in entry.aliases.iter().enumerate() {
+                match aliases.entry(alias) {
+                    Entry::Occupied(first) => errors.push(ValidationError {
+                        id: entry.id.clone(),
+                        field: format!("aliases[{i}]"),
+                        kind: ValidationErrorKind::DuplicateAlias(first.get().to_string()),
+                    }),
+                    Entry::Vacant(slot) => {
+                        slot.insert(entry.id.as_str());
+                    }
+                }
+            }
+            for (i, substitute) in entry.substitutes.iter().enumerate() {
+                if self.get(substitute).is_none() {
+                    errors.push(ValidationError {
+                        id: entry.id.clone(),
+                        field: format!("substitutes[{i}]"),
+                        kind: ValidationErrorKind::UnknownSubstitute(substitute.clone()),
+                    });
+                }
+            }
+        }
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Allergen, IngredientCategory, Month, TestEntry};
+    use std::path::Path;
+
+    fn egg() -> IngredientEntry {
+        TestEntry::new("chicken_egg", "protein")
+            .with("name.en", "Chicken Egg")
+            .with(
+                "composition",
+                serde_json::json!({
+                    "water": 76.15, "protein": 12.56, "total_fat": 9.51,
+                    "saturated_fat": 3.13, "carbohydrates": 0.72,
+                    "fiber": 0.0, "sugar": 0.37, "ph": 7.6
+                }),
+            )
+            .with("allergens", vec!["eggs"])
+            .with("seasonal_availability", vec!["Mar", "Apr", "May"])
+            .with("sources", vec!["USDA FoodData Central #171287"])
+            .entry()
+    }
+
+    fn fields(errors: &[ValidationError]) -> Vec<&str> {
+        errors.iter().map(|e| e.field.as_str()).collect()
+    }
+
+    #[test]
+    fn test_repository_data_is_valid() {
+        let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../sid/data");
+        let mut client = SidClient::new();
+        if let Err(errors) = client.load_dir(&data) {
+            let messages: Vec<_> = errors.iter().map(ToString::to_string).collect();
+            panic!("failed to load SID data:\n{}", messages.join("\n"));
+        }
+        let errors: Vec<_> = client
+            .validate()
+            .into_iter()
+            .filter(|e| !e.kind.is_warning())
+            .collect();
+        let messages: Vec<_> = errors.iter().map(ToString::to_string).collect();
+        assert!(
+            errors.is_empty(),
+            "invalid SID data:\n{}",
+            messages.join("\n")
+        );
+    }
+
+    #[test]
+    fn test_valid_entry() {
+        assert_eq!(validate(&egg()), vec![]);
+    }
+
+    #[test]
+    fn test_ranges_and_sum() {
+        let mut entry = egg();
+        entry.composition.protein = -1.0;
+        entry.composition.ph = Some(15.0);
+        entry.composition.water = 95.0;
+        let errors = validate(&entry);
+        assert_eq!(
+            fields(&errors),
+            ["composition.protein", "composition.ph", "composition"]
+        );
+        assert!(matches!(
+            errors[0].kind,
+            ValidationErrorKind::OutOfRange { value, .. } if value == -1.0
+        ));
+        assert_eq!(
+            errors[0].to_string(),
+            "chicken_egg: composition.protein must be between 0 and 100, got -1"
+        );
+    }
+
+    #[test]
+    fn test_id_and_sources() {
+        let mut entry = egg();
+        entry.id = "Chicken-Egg".into();
+        entry.sources.clear();
+        let errors = validate(&entry);
+        assert_eq!(fields(&errors), ["id", "sources"]);
+        assert_eq!(errors[1].kind, ValidationErrorKind::MissingSources);
+    }
+
+    #[test]
+    fn test_enums_are_typed() {
+        let entry = egg();
+        assert_eq!(entry.category, IngredientCategory::Protein);
+        assert_eq!(entry.allergens, [Allergen::Eggs]);
+        assert_eq!(entry.seasonal_availability[0], Month::Mar);
+
+        let mut json = serde_json::to_value(egg()).unwrap();
+        json["category"] = "meat".into();
+        assert!(serde_json::from_value::<IngredientEntry>(json.clone()).is_err());
+        json["category"] = "protein".into();
+        json["seasonal_availability"] = serde_json::json!(["March"]);
+        assert!(serde_json::from_value::<IngredientEntry>(json.clone()).is_err());
+        json["seasonal_availability"] = serde_json::json!([]);
+        json["allergens"] = serde_json::json!(["nightshades"]);
+        assert!(serde_json::from_value::<IngredientEntry>(json).is_err());
+    }
+
+    #[test]
+    fn test_substitutes_must_exist() {
+        let mut egg = egg();
+        egg.substitutes = vec!["duck_egg".into(), "quail_egg".into()];
+        let mut duck = egg.clone();
+        duck.id = "duck_egg".into();
+        duck.substitutes = vec!["chicken_egg".into()];
+        let mut client = SidClient::new();
+        client
+            .load_json(&serde_json::to_string(&[egg, duck]).unwrap())
+            .unwrap();
+
+        let errors = client.validate();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "substitutes[1]");
+        assert_eq!(
+            errors[0].kind,
+            ValidationErrorKind::UnknownSubstitute("quail_egg".into())
+        );
+        assert!(errors[0].kind.is_warning());
+    }
+
+    #[test]
+    fn test_aliases() {
+        let mut egg = egg();
+        egg.aliases = serde_json::from_str(r#"[{ "type": "Egg" }, { "type": "egg" }]"#).unwrap();
+        let mut duck = egg.clone();
+        duck.id = "duck_egg".into();
+        duck.aliases.truncate(1);
+        let mut client = SidClient::new();
+        client
+            .load_json(&serde_json::to_string(&[egg, duck]).unwrap())
+            .unwrap();
+
+        let errors = client.validate();
+        assert_eq!(
+            errors.iter().map(|e| &e.kind).collect::<Vec<_>>(),
+            [
+                &ValidationErrorKind::InvalidAliasType("egg".into()),
+                &ValidationErrorKind::DuplicateAlias("chicken_egg".into()),
+            ]
+        );
+        assert_eq!(errors[1].id, "duck_egg");
+    }
+
+    #[test]
+    fn test_provenance_and_revisions() {
+        let mut entry = egg();
+        entry.provenance = serde_json::from_str(
+            r#"{
+                "composition": "USDA FoodData Central #171287",
+                "composition.colour": "USDA FoodData Central #171287",
+                "physical.density_g_per_ml": "Wikipedia"
+            }"#,
+        )
+        .unwrap();
+        entry.revisions = serde_json::from_str(
+            r#"[
+                { "revision": 1, "date": "2024-01-15", "summary": "Initial entry" },
+                { "revision": 1, "date": "15/03/2025", "summary": "Add pH" }
+            ]"#,
+        )
+        .unwrap();
+        let errors = validate(&entry);
+        assert_eq!(
+            fields(&errors),
+            [
+                "provenance.composition.colour",
+                "provenance.physical.density_g_per_ml",
+                "revisions[1].revision",
+                "revisions[1].date"
+            ]
+        );
+        assert_eq!(errors[0].kind, ValidationErrorKind::UnknownField);
+        assert_eq!(
+            errors[1].kind,
+            ValidationErrorKind::UnknownSource("Wikipedia".into())
+        );
+        assert_eq!(
+            errors[2].to_string(),
+            "chicken_egg: revisions[1].revision revision 1 does not follow revision 1"
+        );
+    }
+
+    #[test]
+    fn test_denaturation() {
+        let mut entry = egg();
+        entry.denaturation = serde_json::from_str(
+            r#"[
+                { "part": "white", "system": "egg_white", "fractions": [
+                    { "protein": "ovalbumin", "share": 1.2,
+                      "activation_energy_j_per_mol": 0.0, "reference_temp_c": 80.0,
+                      "reference_rate_per_s": 0.004 },
+                    { "protein": "lysozyme", "share": 0.1,
+                      "activation_energy_j_per_mol": 450000.0, "reference_temp_c": 80.0,
+                      "reference_rate_per_s": -0.01 }
+                ] },
+                { "part": "shell", "system": "shell", "fractions": [] }
+            ]"#,
+        )
+        .unwrap();
+        let errors = validate(&entry);
+        assert_eq!(
+            fields(&errors),
+            [
+                "denaturation[0].fractions[0].share",
+                "denaturation[0].fractions[0].activation_energy_j_per_mol",
+                "denaturation[0].fractions[1].protein",
+                "denaturation[0].fractions[1].reference_rate_per_s",
+                "denaturation[1].system",
+                "denaturation[1].fractions"
+            ]
+        );
+        assert_eq!(errors[1].kind, ValidationErrorKind::NotPositive(0.0));
+        assert_eq!(
+            errors[4].to_string(),
+            "chicken_egg: denaturation[1].system must be one of meat, egg_white, egg_yolk, got `shell`"
+        );
+    }
+
+    #[test]
+    fn test_unknown_fields_are_rejected() {
+        let json = serde_json::to_value(egg()).unwrap();
+        let mut object = json.as_object().unwrap().clone();
+        object.insert("colour".into(), "white".into());
+        assert!(serde_json::from_value::<IngredientEntry>(object.into()).is_err());
+    }
+}
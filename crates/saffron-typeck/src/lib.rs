@@ -7,6 +7,24 @@
 //! - Trait bound verification
 //! - Process-ingredient compatibility checking
 
+use saffron_ast::Unit;
+use saffron_physics::units::Dimension;
+use thiserror::Error;
+
+/// Type errors
+#[derive(Debug, Error)]
+pub enum TypeError {
+    #[error(
+        "Unit mismatch: cannot compare {left:?} ({left_dim:?}) with {right:?} ({right_dim:?})"
+    )]
+    UnitMismatch {
+        left: Unit,
+        right: Unit,
+        left_dim: Dimension,
+        right_dim: Dimension,
+    },
+}
+
 pub struct TypeChecker {
     // TODO: Phase 1 implementation
 }
@@ -15,6 +33,28 @@ impl TypeChecker {
     pub fn new() -> Self {
         Self {}
     }
+
+    /// Dimensional analysis for comparisons and arguments: `oil.temp >= 180.celsius`
+    /// is fine against Fahrenheit but not against grams
+    pub fn check_units(&self, left: &Unit, right: &Unit) -> Result<(), TypeError> {
+        let (left_dim, right_dim) = (Dimension::of(left), Dimension::of(right));
+        if left_dim == right_dim {
+            Ok(())
+        } else {
+            Err(TypeError::UnitMismatch {
+                left: left.clone(),
+                right: right.clone(),
+                left_dim,
+                right_dim,
+            })
+        }
+    }
+}
+
+impl Default for TypeChecker {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[cfg(test)]
@@ -25,4 +65,19 @@ mod tests {
     fn test_typechecker_creation() {
         let _tc = TypeChecker::new();
     }
+
+    #[test]
+    fn test_unit_dimensions() {
+        let tc = TypeChecker::new();
+        assert!(tc.check_units(&Unit::Celsius, &Unit::Fahrenheit).is_ok());
+        assert!(tc.check_units(&Unit::Cups, &Unit::Milliliters).is_ok());
+        assert!(matches!(
+            tc.check_units(&Unit::Celsius, &Unit::Grams),
+            Err(TypeError::UnitMismatch {
+                left_dim: Dimension::Temperature,
+                right_dim: Dimension::Mass,
+                ..
+            })
+        ));
+    }
 }
@@ -5,13 +5,178 @@
 //! - ~50 opcodes for culinary operations
 //! - Tracing garbage collector
 //! - Async event loop for temporal operations
+//! - Seeded, reproducible stochastic variation (see [`SimulationConfig`])
+
+use saffron_ast::{ExpectedResult, Expr};
+pub use saffron_physics::simulation::{BatchReport, MonteCarlo, ParameterSet, SimulationConfig};
+use std::collections::BTreeMap;
+
+/// A property value produced by a simulation run
+#[derive(Debug, Clone, PartialEq)]
+pub enum Observed {
+    /// Enum state such as `TextureState.Set`, by variant name
+    Variant(String),
+    Number(f64),
+    Bool(bool),
+}
 
 pub struct SaffronVM {
+    config: SimulationConfig,
     // TODO: Phase 2 implementation
 }
 
+impl Default for SaffronVM {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SaffronVM {
     pub fn new() -> Self {
-        Self {}
+        Self::with_config(SimulationConfig::default())
+    }
+
+    pub fn with_config(config: SimulationConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &SimulationConfig {
+        &self.config
+    }
+
+    /// Parameter values for a single reproducible run
+    pub fn parameters(&self, run: u64) -> ParameterSet {
+        self.config.sample(run)
+    }
+
+    /// Run `simulate` `runs` times and report how often each `expected_result` property was hit
+    pub fn monte_carlo<F>(
+        &self,
+        expected: &ExpectedResult,
+        runs: usize,
+        mut simulate: F,
+    ) -> BatchReport
+    where
+        F: FnMut(&ParameterSet) -> BTreeMap<String, Observed>,
+    {
+        let properties: Vec<&str> = expected
+            .properties
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        MonteCarlo::new(self.config.clone(), runs).run(&properties, |params| {
+            let observed = simulate(params);
+            expected
+                .properties
+                .iter()
+                .map(|p| {
+                    let hit = observed
+                        .get(&p.name)
+                        .is_some_and(|value| self.matches(&p.value, value));
+                    (p.name.clone(), hit)
+                })
+                .collect()
+        })
+    }
+
+    fn matches(&self, expected: &Expr, observed: &Observed) -> bool {
+        match (expected, observed) {
+            (Expr::EnumVariant { variant, .. }, Observed::Variant(v)) => variant == v,
+            // Qualified form: TextureState.Set
+            (Expr::FieldAccess { field, .. }, Observed::Variant(v)) => field == v,
+            (Expr::UnitLiteral { value, .. }, Observed::Number(n))
+            | (Expr::NumericLiteral { value, .. }, Observed::Number(n))
+            | (Expr::PercentLiteral { value, .. }, Observed::Number(n)) => {
+                self.config.matches(*value, *n)
+            }
+            (Expr::BoolLiteral { value, .. }, Observed::Bool(b)) => value == b,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use saffron_ast::{Param, Span, TypeRef};
+    use saffron_physics::simulation::Distribution;
+
+    fn span() -> Span {
+        Span {
+            file: "test.saffron".into(),
+            start_line: 1,
+            start_col: 1,
+            end_line: 1,
+            end_col: 1,
+            byte_offset: 0,
+            byte_length: 0,
+        }
+    }
+
+    fn soft_boiled_egg() -> ExpectedResult {
+        let param = |name: &str, value: Expr| Param {
+            name: name.into(),
+            value,
+            span: span(),
+        };
+        ExpectedResult {
+            type_ref: TypeRef {
+                name: "BoiledEgg".into(),
+                generics: vec![],
+                span: span(),
+            },
+            properties: vec![
+                param(
+                    "yolk",
+                    Expr::EnumVariant {
+                        variant: "Runny".into(),
+                        span: span(),
+                    },
+                ),
+                param(
+                    "whites",
+                    Expr::EnumVariant {
+                        variant: "Set".into(),
+                        span: span(),
+                    },
+                ),
+            ],
+            span: span(),
+        }
+    }
+
+    /// Toy model: bigger and colder eggs need longer than a fixed 6 minutes
+    fn six_minute_egg(params: &ParameterSet) -> BTreeMap<String, Observed> {
+        let mass = params.get_or("egg_mass_g", 57.0);
+        let fridge = params.get_or("fridge_temp_c", 4.0);
+        let needed_s = 360.0 * (mass / 57.0).powf(2.0 / 3.0) * (1.0 + (4.0 - fridge) * 0.01);
+        let yolk = if needed_s >= 360.0 { "Runny" } else { "Jammy" };
+        BTreeMap::from([
+            ("yolk".to_string(), Observed::Variant(yolk.into())),
+            ("whites".to_string(), Observed::Variant("Set".into())),
+        ])
+    }
+
+    #[test]
+    fn test_monte_carlo_is_reproducible() {
+        let vm = SaffronVM::with_config(SimulationConfig::kitchen(2024));
+        let first = vm.monte_carlo(&soft_boiled_egg(), 500, six_minute_egg);
+        let second = vm.monte_carlo(&soft_boiled_egg(), 500, six_minute_egg);
+        assert_eq!(first, second);
+        assert_eq!(first.probability("whites"), Some(1.0));
+        let yolk = first.probability("yolk").unwrap();
+        assert!(yolk > 0.2 && yolk < 0.8, "{yolk}");
+        assert_eq!(first.all_hit, first.hits["yolk"]);
+    }
+
+    #[test]
+    fn test_deterministic_config_always_hits() {
+        let config = SimulationConfig::new(7)
+            .with_parameter("egg_mass_g", Distribution::Fixed(60.0))
+            .with_parameter("fridge_temp_c", Distribution::Fixed(4.0));
+        let vm = SaffronVM::with_config(config);
+        let report = vm.monte_carlo(&soft_boiled_egg(), 20, six_minute_egg);
+        assert_eq!(report.overall_probability(), 1.0);
+        assert_eq!(vm.parameters(3).get("egg_mass_g"), Some(60.0));
     }
 }